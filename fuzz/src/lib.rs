@@ -0,0 +1,323 @@
+//! The checks run by the fuzz targets.
+//!
+//! The checks live here rather than in the targets themselves so that the
+//! regression tests of the main crate can replay the corpus in
+//! `fuzz/regressions` through them. The tests include this file directly,
+//! so it must only depend on _octseq_ and the standard library.
+
+use octseq::parse::{ParseStrError, Parser};
+use octseq::str::StrBuilder;
+use octseq::Array;
+
+
+//------------ parser --------------------------------------------------------
+
+/// Runs a sequence of operations on a parser and a reference model.
+///
+/// The first octet of `data` gives the length of the octets to parse,
+/// which follow immediately. The next two octets select the range of the
+/// parser within these octets. The rest of the data are operations, each
+/// consisting of an opcode and an argument octet.
+///
+/// After each operation, the method checks that the parser returned the
+/// same result as the model and that both are in the same state.
+pub fn parser(data: &[u8]) {
+    let Some((&len, data)) = data.split_first() else { return };
+    let (octets, data) = data.split_at(usize::from(len).min(data.len()));
+    let (start, end, mut ops) = match *data {
+        [start, end, ref ops @ ..] => {
+            let start = usize::from(start) % (octets.len() + 1);
+            let end = usize::from(end) % (octets.len() + 1);
+            (start.min(end), start.max(end), ops)
+        }
+        _ => (0, octets.len(), b"".as_ref()),
+    };
+
+    let mut parser = Parser::with_range(octets, start..end);
+    let mut model = Model { octets, pos: start, len: end };
+    while let [op, arg, ref tail @ ..] = *ops {
+        ops = tail;
+        let len = decode_len(arg);
+        let small = usize::from(arg);
+        match op % 23 {
+            0 => {
+                assert_eq!(
+                    parser.advance(len).ok(),
+                    model.take(len).map(|_| ())
+                );
+            }
+            1 => {
+                let res = parser.seek(len).ok();
+                assert_eq!(res.is_some(), len <= model.len);
+                if res.is_some() {
+                    model.pos = len;
+                }
+            }
+            2 => {
+                parser.advance_to_end();
+                model.pos = model.len;
+            }
+            3 => {
+                assert_eq!(parser.peek(len).ok(), model.peek(len));
+            }
+            4 => {
+                assert_eq!(parser.peek_back(len).ok(), model.peek_back(len));
+            }
+            5 => {
+                assert_eq!(parser.parse_octets(len).ok(), model.take(len));
+            }
+            6 => {
+                assert_eq!(
+                    parser.parse_octets_back(len).ok(),
+                    model.take_back(len)
+                );
+            }
+            7 => {
+                let mut buf = vec![0; small];
+                assert_eq!(
+                    parser.parse_buf(&mut buf).ok().map(|_| buf.as_slice()),
+                    model.take(small)
+                );
+            }
+            8 => {
+                let mut buf = vec![0; small];
+                assert_eq!(
+                    parser.parse_buf_back(&mut buf).ok().map(|_| {
+                        buf.as_slice()
+                    }),
+                    model.take_back(small)
+                );
+            }
+            9 => {
+                let pos = model.pos;
+                match (parser.parse_parser(len).ok(), model.take(len)) {
+                    (Some(sub), Some(octets)) => {
+                        assert_eq!(sub.pos(), pos);
+                        assert_eq!(sub.peek_all(), octets);
+                    }
+                    (None, None) => { }
+                    (left, right) => panic!("{left:?} != {right:?}"),
+                }
+            }
+            10 => {
+                match (
+                    parser.parse_parser_back(len).ok(), model.take_back(len)
+                ) {
+                    (Some(sub), Some(octets)) => {
+                        assert_eq!(sub.pos(), model.len);
+                        assert_eq!(sub.peek_all(), octets);
+                    }
+                    (None, None) => { }
+                    (left, right) => panic!("{left:?} != {right:?}"),
+                }
+            }
+            11 => {
+                assert_eq!(
+                    parser.retreat_end(len).ok(),
+                    model.take_back(len).map(|_| ())
+                );
+            }
+            12 => {
+                assert_eq!(
+                    parser.check_len(len).ok(), model.peek(len).map(|_| ())
+                );
+            }
+            13 => {
+                assert_eq!(
+                    parser.parse_u8().ok(),
+                    model.take(1).map(|octets| octets[0])
+                );
+            }
+            14 => {
+                assert_eq!(
+                    parser.parse_u16_be().ok(),
+                    model.take_array().map(u16::from_be_bytes)
+                );
+            }
+            15 => {
+                assert_eq!(
+                    parser.parse_i32_le().ok(),
+                    model.take_array().map(i32::from_le_bytes)
+                );
+            }
+            16 => {
+                assert_eq!(
+                    parser.parse_u64_be().ok(),
+                    model.take_array().map(u64::from_be_bytes)
+                );
+            }
+            17 => {
+                assert_eq!(
+                    parser.parse_u128_le().ok(),
+                    model.take_array().map(u128::from_le_bytes)
+                );
+            }
+            18 => {
+                assert_eq!(
+                    parser.parse_u16_le_back().ok(),
+                    model.take_array_back().map(u16::from_le_bytes)
+                );
+            }
+            19 => {
+                assert_eq!(
+                    parser.parse_i64_be_back().ok(),
+                    model.take_array_back().map(i64::from_be_bytes)
+                );
+            }
+            20 => {
+                check_str(parser.parse_str(len), model.take_str(len));
+            }
+            21 => {
+                let pos = model.pos;
+                let res = match model.take(1) {
+                    Some(len) => {
+                        let res = model.take_str(len[0].into());
+                        if res.is_err() {
+                            model.pos = pos;
+                        }
+                        res
+                    }
+                    None => Err(None),
+                };
+                check_str(parser.parse_str_u8_prefixed(), res);
+            }
+            _ => {
+                let pos = model.pos;
+                let res = match model.peek_all().iter().position(|&ch| {
+                    ch == 0
+                }) {
+                    Some(len) => {
+                        let res = model.take_str(len);
+                        if res.is_ok() {
+                            model.pos += 1;
+                        }
+                        res
+                    }
+                    None => Err(None),
+                };
+                assert!(res.is_ok() || model.pos == pos);
+                check_str(parser.parse_str_nul_terminated(), res);
+            }
+        }
+        assert_eq!(parser.pos(), model.pos);
+        assert_eq!(parser.len(), model.len);
+        assert_eq!(parser.remaining(), model.len - model.pos);
+        assert_eq!(parser.peek_all(), model.peek_all());
+    }
+}
+
+/// Decodes a length argument.
+///
+/// The largest values are mapped to lengths close to `usize::MAX` in order
+/// to catch overflows.
+fn decode_len(arg: u8) -> usize {
+    if arg >= 0xF0 {
+        usize::MAX - usize::from(arg - 0xF0)
+    }
+    else {
+        arg.into()
+    }
+}
+
+/// Compares the result of parsing a string with that of the model.
+///
+/// The model returns `Err(None)` for short input and `Err(Some(offset))`
+/// for invalid UTF-8 starting at `offset`.
+fn check_str(
+    left: Result<impl AsRef<str>, ParseStrError>,
+    right: Result<&str, Option<usize>>,
+) {
+    match (left, right) {
+        (Ok(left), Ok(right)) => assert_eq!(left.as_ref(), right),
+        (Err(ParseStrError::ShortInput(_)), Err(None)) => { }
+        (
+            Err(ParseStrError::InvalidUtf8 { offset, .. }),
+            Err(Some(right))
+        ) => {
+            assert_eq!(offset, right)
+        }
+        (left, right) => {
+            panic!(
+                "{:?} != {:?}", left.map(|s| s.as_ref().to_owned()), right
+            )
+        }
+    }
+}
+
+
+//------------ Model ---------------------------------------------------------
+
+/// A straightforward model of a parser.
+struct Model<'a> {
+    octets: &'a [u8],
+    pos: usize,
+    len: usize,
+}
+
+impl<'a> Model<'a> {
+    fn peek_all(&self) -> &'a [u8] {
+        &self.octets[self.pos..self.len]
+    }
+
+    fn peek(&self, len: usize) -> Option<&'a [u8]> {
+        self.peek_all().get(..len)
+    }
+
+    fn peek_back(&self, len: usize) -> Option<&'a [u8]> {
+        let all = self.peek_all();
+        all.get(all.len().checked_sub(len)?..)
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let res = self.peek(len)?;
+        self.pos += len;
+        Some(res)
+    }
+
+    fn take_back(&mut self, len: usize) -> Option<&'a [u8]> {
+        let res = self.peek_back(len)?;
+        self.len -= len;
+        Some(res)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N).map(|octets| octets.try_into().unwrap())
+    }
+
+    fn take_array_back<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take_back(N).map(|octets| octets.try_into().unwrap())
+    }
+
+    /// Takes a string, leaving the model untouched on error.
+    fn take_str(&mut self, len: usize) -> Result<&'a str, Option<usize>> {
+        let octets = self.peek(len).ok_or(None)?;
+        let res = std::str::from_utf8(octets).map_err(|err| {
+            Some(self.pos + err.valid_up_to())
+        })?;
+        self.pos += len;
+        Ok(res)
+    }
+}
+
+
+//------------ utf8_lossy ----------------------------------------------------
+
+/// Compares `StrBuilder::from_utf8_lossy` with the standard library.
+///
+/// The data is converted both into a `Vec<u8>` and, via
+/// `StrBuilder::try_from_utf8_lossy`, into an array which has to fail if
+/// the converted string doesn’t fit.
+pub fn utf8_lossy(data: &[u8]) {
+    let expected = String::from_utf8_lossy(data);
+
+    let res = StrBuilder::from_utf8_lossy(Vec::from(data));
+    assert_eq!(res.as_str(), expected);
+
+    // If the data is valid UTF-8, try_from_utf8_lossy just takes the
+    // array, so it has to fit.
+    let Ok(array) = Array::<64>::try_from(data) else { return };
+    match StrBuilder::try_from_utf8_lossy(array) {
+        Ok(res) => assert_eq!(res.as_str(), expected),
+        Err(_) => assert!(expected.len() > 64),
+    }
+}
@@ -1,36 +1,87 @@
 //! A fixed-capacity octets sequence.
+//!
+//! The type [`Array<N>`] stores up to `N` octets inline without allocating.
+//! Apart from being an octets builder, it provides many of the methods of
+//! `Vec<u8>` for manipulating its content. Because the capacity is fixed,
+//! all methods that could grow the array return [`ShortBuf`] if there isn’t
+//! enough space.
+//!
+//! Arrays can be created in const context via [`Array::from_array`] and
+//! [`Array::from_slice_const`] or, more conveniently, the
+//! [`array!`][crate::array!] macro.
 
 use core::{cmp, fmt};
-use core::ops::RangeBounds;
+use core::ops::{Bound, RangeBounds};
+#[cfg(feature = "serde")] use core::marker::PhantomData;
 use crate::builder::{
     EmptyBuilder, FreezeBuilder, FromBuilder, IntoBuilder, OctetsBuilder,
     ShortBuf, Truncate,
 };
+use crate::encoding::hexdump;
 use crate::octets::{Octets, OctetsFrom};
 
 
 //------------ Array ---------------------------------------------------------
 
+/// An octets builder and sequence atop an array of `N` octets.
+///
+/// The second type argument `L` determines the type used for storing the
+/// current length of the array. It defaults to `usize` but can be set to
+/// any type implementing [`ArrayLen`], i.e., `u8`, `u16`, or `u32`, to
+/// reduce the memory footprint of the array. For instance, an
+/// `Array<16, u8>` occupies 17 octets while an `Array<16>` needs 24 octets
+/// on 64 bit systems.
+///
+/// Using a length type that is too small for `N` results in a compile
+/// time error when the array is created.
 #[derive(Clone)]
-pub struct Array<const N: usize> {
+pub struct Array<const N: usize, L: ArrayLen = usize> {
     octets: [u8; N],
-    len: usize
+    len: L,
 }
 
-impl<const N: usize> Array<N> {
+impl<const N: usize, L: ArrayLen> Array<N, L> {
+    /// Ensures that `N` can be represented by the length type.
+    const LEN_FITS: () = assert!(
+        N <= L::MAX, "array capacity too large for the length type"
+    );
+
     /// Creates a new empty value.
-    pub fn new() -> Self {
-        Default::default()
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::LEN_FITS;
+        Array {
+            octets: [0; N],
+            len: L::ZERO,
+        }
+    }
+
+    /// Returns the number of octets in the array.
+    pub fn len(&self) -> usize {
+        self.len.to_usize()
+    }
+
+    /// Returns whether the array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets the length of the array.
+    ///
+    /// The caller must make sure that `len` is not larger than `N`.
+    fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= N);
+        self.len = L::from_usize(len);
     }
 
     /// Returns an octets slice with the content of the array.
     pub fn as_slice(&self) -> &[u8] {
-        &self.octets[..self.len]
+        &self.octets[..self.len()]
     }
 
     /// Returns a mutable octets slice with the content of the array.
     pub fn as_slice_mut(&mut self) -> &mut [u8] {
-        &mut self.octets[..self.len]
+        &mut self.octets[..self.len.to_usize()]
     }
 
     /// Resizes the array in place updating additional octets.
@@ -40,34 +91,351 @@ impl<const N: usize> Array<N> {
     /// content of the additional octets will be left at whatever they
     /// were.
     ///
+    /// If the `zeroize` feature is enabled and `new_len` is smaller than
+    /// the current length, the discarded octets are overwritten with zeros.
+    ///
     /// Returns an error if `new_len` is larger than the array size.
     pub fn resize_raw(&mut self, new_len: usize) -> Result<(), ShortBuf> {
         if new_len > N {
             Err(ShortBuf)
         }
         else {
-            self.len = new_len;
+            self.wipe_from(new_len);
+            self.set_len(new_len);
             Ok(())
         }
     }
+
+    /// Returns the capacity of the array.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns whether the array is filled to its capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Removes all octets from the array.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Appends a single octet to the end of the array.
+    ///
+    /// Returns an error if the array is already full.
+    pub fn push(&mut self, octet: u8) -> Result<(), ShortBuf> {
+        self.append_slice(&[octet])
+    }
+
+    /// Removes the last octet from the array and returns it.
+    ///
+    /// Returns `None` if the array is empty.
+    pub fn pop(&mut self) -> Option<u8> {
+        let new_len = self.len().checked_sub(1)?;
+        let res = self.octets[new_len];
+        self.truncate(new_len);
+        Some(res)
+    }
+
+    /// Inserts the content of a slice at position `index`.
+    ///
+    /// All octets after `index` are shifted to the right. Returns an error
+    /// and leaves the array untouched if there isn’t enough space to
+    /// insert the slice.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `index` is greater than the array’s length.
+    pub fn insert(
+        &mut self, index: usize, slice: &[u8]
+    ) -> Result<(), ShortBuf> {
+        self.splice(index..index, slice)
+    }
+
+    /// Removes the octets in the given range.
+    ///
+    /// All octets after the range are shifted to the left.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the range is decreasing or out of bounds.
+    pub fn remove(&mut self, range: impl RangeBounds<usize>) {
+        let (start, end) = self.check_range(range);
+        self.octets.copy_within(end..self.len.to_usize(), start);
+        self.truncate(self.len() - (end - start));
+    }
+
+    /// Replaces the octets in the given range with the content of a slice.
+    ///
+    /// The range and the slice don’t need to be of the same length. The
+    /// octets after the range are shifted as necessary. Returns an error
+    /// and leaves the array untouched if there isn’t enough space for the
+    /// result.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the range is decreasing or out of bounds.
+    pub fn splice(
+        &mut self, range: impl RangeBounds<usize>, replace_with: &[u8]
+    ) -> Result<(), ShortBuf> {
+        let (start, end) = self.check_range(range);
+        let new_len = self.len() - (end - start) + replace_with.len();
+        if new_len > N {
+            return Err(ShortBuf)
+        }
+        let new_end = start + replace_with.len();
+        self.octets.copy_within(end..self.len.to_usize(), new_end);
+        self.octets[start..new_end].copy_from_slice(replace_with);
+        if new_len < self.len() {
+            self.wipe_from(new_len);
+        }
+        self.set_len(new_len);
+        Ok(())
+    }
+
+    /// Appends a copy of the octets in the given range to the end.
+    ///
+    /// Returns an error and leaves the array untouched if there isn’t
+    /// enough space to append the octets.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the range is decreasing or out of bounds.
+    pub fn extend_from_within(
+        &mut self, range: impl RangeBounds<usize>
+    ) -> Result<(), ShortBuf> {
+        let (start, end) = self.check_range(range);
+        let new_len = self.len() + (end - start);
+        if new_len > N {
+            return Err(ShortBuf)
+        }
+        self.octets.copy_within(start..end, self.len.to_usize());
+        self.set_len(new_len);
+        Ok(())
+    }
+
+    /// Retains only the octets for which the closure returns `true`.
+    ///
+    /// The closure is called exactly once for each octet in order. The
+    /// order of the retained octets is preserved.
+    pub fn retain(&mut self, mut op: impl FnMut(&u8) -> bool) {
+        let mut new_len = 0;
+        for idx in 0..self.len() {
+            if op(&self.octets[idx]) {
+                self.octets[new_len] = self.octets[idx];
+                new_len += 1;
+            }
+        }
+        self.truncate(new_len);
+    }
+
+    /// Resizes the array to `new_len` octets.
+    ///
+    /// If `new_len` is greater than the current length, the array is
+    /// extended by octets set to `value`. Otherwise, it is truncated.
+    ///
+    /// Returns an error and leaves the array untouched if `new_len` is
+    /// larger than the array size.
+    pub fn resize(
+        &mut self, new_len: usize, value: u8
+    ) -> Result<(), ShortBuf> {
+        if new_len > N {
+            return Err(ShortBuf)
+        }
+        if new_len > self.len() {
+            self.octets[self.len.to_usize()..new_len].fill(value);
+            self.set_len(new_len);
+        }
+        else {
+            self.truncate(new_len);
+        }
+        Ok(())
+    }
+
+    /// Overwrites all octets currently in the array with `value`.
+    ///
+    /// The length of the array stays unchanged. Use
+    /// [`resize`][Self::resize] to extend the array with a given value.
+    pub fn fill(&mut self, value: u8) {
+        self.as_slice_mut().fill(value)
+    }
+
+    /// Converts a range into its start and end within the current content.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the range is decreasing or out of bounds.
+    fn check_range(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(n) => *n,
+            Bound::Excluded(n) => n.checked_add(1).expect("range overflow"),
+        };
+        let end = match range.end_bound() {
+            Bound::Unbounded => self.len(),
+            Bound::Excluded(n) => *n,
+            Bound::Included(n) => n.checked_add(1).expect("range overflow"),
+        };
+        assert!(start <= end, "range start is greater than range end");
+        assert!(end <= self.len(), "range end is out of bounds");
+        (start, end)
+    }
+
+    /// Overwrites the octets from `start` to the current length with zeros.
+    ///
+    /// This only happens if the `zeroize` feature is enabled. Otherwise,
+    /// the method does nothing.
+    fn wipe_from(&mut self, start: usize) {
+        #[cfg(feature = "zeroize")]
+        if start < self.len() {
+            let end = self.len();
+            zeroize::Zeroize::zeroize(&mut self.octets[start..end]);
+        }
+        #[cfg(not(feature = "zeroize"))]
+        let _ = start;
+    }
+}
+
+
+impl<const N: usize> Array<N> {
+    /// Creates a new value containing the octets of an array.
+    ///
+    /// The array must not be longer than `N`. This is checked at compile
+    /// time.
+    ///
+    /// Like [`from_slice_const`][Self::from_slice_const], the function is
+    /// only available for arrays with the default length type `usize`.
+    pub const fn from_array<const M: usize>(array: [u8; M]) -> Self {
+        const {
+            assert!(M <= N, "array too long");
+        }
+        Self::from_slice_const(&array)
+    }
+
+    /// Creates a new value containing the octets of a slice.
+    ///
+    /// This function is intended to be used in const context, e.g., when
+    /// defining a constant or static value. For all other cases,
+    /// `TryFrom<&[u8]>` is likely more convenient.
+    ///
+    /// The function is only available for arrays with the default length
+    /// type `usize`, since the length can’t be converted into any other
+    /// [`ArrayLen`] type in const context.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if the slice is longer than `N`. If the
+    /// function is evaluated in const context, this results in a compile
+    /// time error.
+    pub const fn from_slice_const(slice: &[u8]) -> Self {
+        assert!(slice.len() <= N, "slice too long for array");
+        let mut res = Self::new();
+        let mut i = 0;
+        while i < slice.len() {
+            res.octets[i] = slice[i];
+            i += 1;
+        }
+        res.len = slice.len();
+        res
+    }
 }
 
 
 //--- Default
 
-impl<const N: usize> Default for Array<N> {
+impl<const N: usize, L: ArrayLen> Default for Array<N, L> {
     fn default() -> Self {
-        Array {
-            octets: [0; N],
-            len: 0
+        Self::new()
+    }
+}
+
+
+//--- From and TryFrom
+
+impl<const N: usize, const M: usize, L: ArrayLen> From<[u8; M]>
+for Array<N, L> {
+    /// Converts an array of octets.
+    ///
+    /// The conversion fails to compile if `M` is larger than `N`.
+    fn from(src: [u8; M]) -> Self {
+        const {
+            assert!(M <= N, "array too long");
         }
+        let mut res = Self::new();
+        res.octets[..M].copy_from_slice(&src);
+        res.set_len(M);
+        res
     }
 }
 
+impl<const N: usize, const M: usize, L: ArrayLen> TryFrom<Array<N, L>>
+for [u8; M] {
+    type Error = Array<N, L>;
 
-//--- TryFrom
+    /// Converts the array into an array of exactly `M` octets.
+    ///
+    /// If the length of the array is not exactly `M`, returns the array
+    /// as the error.
+    fn try_from(src: Array<N, L>) -> Result<Self, Self::Error> {
+        src.as_slice().try_into().map_err(|_| src)
+    }
+}
 
-impl<'a, const N: usize> TryFrom<&'a [u8]> for Array<N> {
+#[cfg(feature = "heapless")]
+impl<const N: usize, L: ArrayLen> From<heapless::Vec<u8, N>>
+for Array<N, L> {
+    fn from(src: heapless::Vec<u8, N>) -> Self {
+        let mut res = Self::new();
+        res.octets[..src.len()].copy_from_slice(&src);
+        res.set_len(src.len());
+        res
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize, L: ArrayLen> From<Array<N, L>>
+for heapless::Vec<u8, N> {
+    fn from(src: Array<N, L>) -> Self {
+        let mut res = heapless::Vec::new();
+        res.extend_from_slice(src.as_slice()).expect(
+            "array shorter than capacity"
+        );
+        res
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A, const N: usize, L> TryFrom<smallvec::SmallVec<A>> for Array<N, L>
+where
+    A: smallvec::Array<Item = u8>,
+    L: ArrayLen,
+{
+    type Error = smallvec::SmallVec<A>;
+
+    /// Converts a small vec into an array.
+    ///
+    /// If the small vec is longer than `N`, returns it as the error.
+    fn try_from(src: smallvec::SmallVec<A>) -> Result<Self, Self::Error> {
+        Self::try_from(src.as_slice()).map_err(|_| src)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<const N: usize, L> From<Array<N, L>> for smallvec::SmallVec<[u8; N]>
+where
+    [u8; N]: smallvec::Array<Item = u8>,
+    L: ArrayLen,
+{
+    /// Converts the array into a small vec with the same inline capacity.
+    ///
+    /// Because the capacities are the same, this never allocates.
+    fn from(src: Array<N, L>) -> Self {
+        smallvec::SmallVec::from_slice(src.as_slice())
+    }
+}
+
+impl<'a, const N: usize, L: ArrayLen> TryFrom<&'a [u8]> for Array<N, L> {
     type Error = ShortBuf;
 
     fn try_from(src: &'a [u8]) -> Result<Self, ShortBuf> {
@@ -78,7 +446,7 @@ impl<'a, const N: usize> TryFrom<&'a [u8]> for Array<N> {
         else {
             let mut res = Self::default();
             res.octets[..len].copy_from_slice(src);
-            res.len = len;
+            res.set_len(len);
             Ok(res)
         }
     }
@@ -87,7 +455,7 @@ impl<'a, const N: usize> TryFrom<&'a [u8]> for Array<N> {
 
 //--- Deref, AsRef, Borrow, and Mut versions
 
-impl<const N: usize> core::ops::Deref for Array<N> {
+impl<const N: usize, L: ArrayLen> core::ops::Deref for Array<N, L> {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
@@ -95,39 +463,71 @@ impl<const N: usize> core::ops::Deref for Array<N> {
     }
 }
 
-impl<const N: usize> core::ops::DerefMut for Array<N> {
+impl<const N: usize, L: ArrayLen> core::ops::DerefMut for Array<N, L> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_slice_mut()
     }
 }
 
-impl<const N: usize> AsRef<[u8]> for Array<N> {
+impl<const N: usize, L: ArrayLen> AsRef<[u8]> for Array<N, L> {
     fn as_ref(&self) -> &[u8] {
         self.as_slice()
     }
 }
 
-impl<const N: usize> AsMut<[u8]> for Array<N> {
+impl<const N: usize, L: ArrayLen> AsMut<[u8]> for Array<N, L> {
     fn as_mut(&mut self) -> &mut [u8] {
         self.as_slice_mut()
     }
 }
 
-impl<const N: usize> core::borrow::Borrow<[u8]> for Array<N> {
+impl<const N: usize, L: ArrayLen> core::borrow::Borrow<[u8]> for Array<N, L> {
     fn borrow(&self) -> &[u8] {
         self.as_slice()
     }
 }
 
-impl<const N: usize> core::borrow::BorrowMut<[u8]> for Array<N> {
+impl<const N: usize, L> core::borrow::BorrowMut<[u8]> for Array<N, L>
+where L: ArrayLen {
     fn borrow_mut(&mut self) -> &mut [u8] {
         self.as_slice_mut()
     }
 }
 
-//--- Truncate
+//--- IntoIterator
+
+impl<const N: usize, L: ArrayLen> IntoIterator for Array<N, L> {
+    type Item = u8;
+    type IntoIter = core::iter::Take<core::array::IntoIter<u8, N>>;
 
-impl<const N: usize> Octets for Array<N> {
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        self.octets.into_iter().take(len)
+    }
+}
+
+impl<'a, const N: usize, L: ArrayLen> IntoIterator for &'a Array<N, L> {
+    type Item = &'a u8;
+    type IntoIter = core::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, const N: usize, L: ArrayLen> IntoIterator for &'a mut Array<N, L> {
+    type Item = &'a mut u8;
+    type IntoIter = core::slice::IterMut<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice_mut().iter_mut()
+    }
+}
+
+
+//--- Octets
+
+impl<const N: usize, L: ArrayLen> Octets for Array<N, L> {
     type Range<'a> = &'a [u8];
 
     fn range(&self, range: impl RangeBounds<usize>) -> Self::Range<'_> {
@@ -138,32 +538,39 @@ impl<const N: usize> Octets for Array<N> {
 
 //--- Truncate
 
-impl<const N: usize> Truncate for Array<N> {
+/// Truncates the array.
+///
+/// If the `zeroize` feature is enabled, the discarded octets are
+/// overwritten with zeros.
+impl<const N: usize, L: ArrayLen> Truncate for Array<N, L> {
     fn truncate(&mut self, len: usize) {
-        self.len = cmp::min(self.len, len)
+        self.wipe_from(len);
+        if len < self.len() {
+            self.set_len(len)
+        }
     }
 }
 
 
 //--- OctetsBuilder, EmptyBuilder, and FreezeBuilder
 
-impl<const N: usize> OctetsBuilder for Array<N> {
+impl<const N: usize, L: ArrayLen> OctetsBuilder for Array<N, L> {
     type AppendError = ShortBuf;
 
     fn append_slice(
         &mut self, slice: &[u8]
     ) -> Result<(), Self::AppendError> {
-        let end = self.len + slice.len();
+        let end = self.len() + slice.len();
         if end > N {
             return Err(ShortBuf)
         }
-        self.octets[self.len..end].copy_from_slice(slice);
-        self.len = end;
+        self.octets[self.len.to_usize()..end].copy_from_slice(slice);
+        self.set_len(end);
         Ok(())
     }
 }
 
-impl<const N: usize> EmptyBuilder for Array<N> {
+impl<const N: usize, L: ArrayLen> EmptyBuilder for Array<N, L> {
     fn empty() -> Self {
         Default::default()
     }
@@ -173,7 +580,7 @@ impl<const N: usize> EmptyBuilder for Array<N> {
     }
 }
 
-impl<const N: usize> FreezeBuilder for Array<N> {
+impl<const N: usize, L: ArrayLen> FreezeBuilder for Array<N, L> {
     type Octets = Self;
 
     fn freeze(self) -> Self::Octets {
@@ -184,7 +591,7 @@ impl<const N: usize> FreezeBuilder for Array<N> {
 
 //--- IntoBuilder, FromBuilder
 
-impl<const N: usize> IntoBuilder for Array<N> {
+impl<const N: usize, L: ArrayLen> IntoBuilder for Array<N, L> {
     type Builder = Self;
 
     fn into_builder(self) -> Self::Builder {
@@ -192,7 +599,7 @@ impl<const N: usize> IntoBuilder for Array<N> {
     }
 }
 
-impl<const N: usize> FromBuilder for Array<N> {
+impl<const N: usize, L: ArrayLen> FromBuilder for Array<N, L> {
     type Builder = Self;
 
     fn from_builder(builder: Self::Builder) -> Self {
@@ -203,7 +610,11 @@ impl<const N: usize> FromBuilder for Array<N> {
 
 //--- OctetsFrom
 
-impl<Source: AsRef<[u8]>, const N: usize> OctetsFrom<Source> for Array<N> {
+impl<Source, const N: usize, L> OctetsFrom<Source> for Array<N, L>
+where
+    Source: AsRef<[u8]>,
+    L: ArrayLen,
+{
     type Error = ShortBuf;
 
     fn try_octets_from(source: Source) -> Result<Self, Self::Error> {
@@ -214,24 +625,32 @@ impl<Source: AsRef<[u8]>, const N: usize> OctetsFrom<Source> for Array<N> {
 
 //--- PartialEq and Eq
 
-impl<T: AsRef<[u8]>, const N: usize> PartialEq<T> for Array<N> {
+impl<T, const N: usize, L> PartialEq<T> for Array<N, L>
+where
+    T: AsRef<[u8]>,
+    L: ArrayLen,
+{
     fn eq(&self, other: &T) -> bool {
         self.as_slice().eq(other.as_ref())
     }
 }
 
-impl<const N: usize> Eq for Array<N> { }
+impl<const N: usize, L: ArrayLen> Eq for Array<N, L> { }
 
 
 //--- PartialOrd and Ord
 
-impl<T: AsRef<[u8]>, const N: usize> PartialOrd<T> for Array<N> {
+impl<T, const N: usize, L> PartialOrd<T> for Array<N, L>
+where
+    T: AsRef<[u8]>,
+    L: ArrayLen,
+{
     fn partial_cmp(&self, other: &T) -> Option<cmp::Ordering> {
         self.as_slice().partial_cmp(other.as_ref())
     }
 }
 
-impl<const N: usize> Ord for Array<N> {
+impl<const N: usize, L: ArrayLen> Ord for Array<N, L> {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         self.as_slice().cmp(other)
     }
@@ -240,7 +659,7 @@ impl<const N: usize> Ord for Array<N> {
 
 //--- Hash
 
-impl<const N: usize> core::hash::Hash for Array<N> {
+impl<const N: usize, L: ArrayLen> core::hash::Hash for Array<N, L> {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.as_slice().hash(state)
     }
@@ -249,11 +668,38 @@ impl<const N: usize> core::hash::Hash for Array<N> {
 
 //--- Debug
 
-impl<const N: usize> fmt::Debug for Array<N> {
+/// Formats the array for debugging.
+///
+/// In the alternate form, i.e., when using `{:#?}`, the content is shown
+/// as a hex dump.
+impl<const N: usize, L: ArrayLen> fmt::Debug for Array<N, L> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("octets::Array")
-            .field(&self.as_slice())
-            .finish()
+        if f.alternate() {
+            f.write_str("octets::Array(")?;
+            hexdump(self.as_slice()).fmt_indented(f, "    ", true)?;
+            f.write_str("\n)")
+        }
+        else {
+            f.debug_tuple("octets::Array")
+                .field(&self.as_slice())
+                .finish()
+        }
+    }
+}
+
+
+//--- Zeroize
+
+/// Overwrites the complete array with zeros and sets its length to zero.
+///
+/// The array does not implement `ZeroizeOnDrop`. If the content of the
+/// array should be wiped when it is dropped, wrap it in
+/// `zeroize::Zeroizing`.
+#[cfg(feature = "zeroize")]
+impl<const N: usize, L: ArrayLen> zeroize::Zeroize for Array<N, L> {
+    fn zeroize(&mut self) {
+        self.octets.zeroize();
+        self.len = L::default();
     }
 }
 
@@ -261,7 +707,8 @@ impl<const N: usize> fmt::Debug for Array<N> {
 //--- SerializeOctets and DeserializeOctets
 
 #[cfg(feature = "serde")]
-impl<const N: usize> crate::serde::SerializeOctets for Array<N> {
+impl<const N: usize, L> crate::serde::SerializeOctets for Array<N, L>
+where L: ArrayLen {
     fn serialize_octets<S: serde::Serializer>(
         &self, serializer: S
     ) -> Result<S::Ok, S::Error> {
@@ -269,33 +716,187 @@ impl<const N: usize> crate::serde::SerializeOctets for Array<N> {
     }
 }
 
-#[cfg(feature = "serde")]
-impl<'de, const N: usize> crate::serde::DeserializeOctets<'de> for Array<N> {
-    type Visitor = ArrayVisitor<N>;
+macro_rules! deserialize_octets {
+    ( $( $len:ty => $visitor:ty = $make:expr; )* ) => {
+        $(
+            #[cfg(feature = "serde")]
+            impl<'de, const N: usize> crate::serde::DeserializeOctets<'de>
+            for Array<N, $len> {
+                type Visitor = $visitor;
+
+                fn deserialize_octets<D: serde::Deserializer<'de>>(
+                    deserializer: D
+                ) -> Result<Self, D::Error> {
+                    Self::visitor().deserialize(deserializer)
+                }
+
+                fn deserialize_with_visitor<D, V>(
+                    deserializer: D,
+                    visitor: V,
+                ) -> Result<V::Value, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                    V: serde::de::Visitor<'de>,
+                {
+                    deserializer.deserialize_byte_buf(visitor)
+                }
+
+                fn visitor() -> Self::Visitor {
+                    $make
+                }
+            }
+        )*
+    }
+}
+
+deserialize_octets! {
+    usize => ArrayVisitor<N> = ArrayVisitor;
+    u8 => CompactArrayVisitor<N, u8> = CompactArrayVisitor::new();
+    u16 => CompactArrayVisitor<N, u16> = CompactArrayVisitor::new();
+    u32 => CompactArrayVisitor<N, u32> = CompactArrayVisitor::new();
+}
+
 
-    fn deserialize_octets<D: serde::Deserializer<'de>>(
-        deserializer: D
-    ) -> Result<Self, D::Error> {
-        Self::visitor().deserialize(deserializer)
+//--- Arbitrary
+
+/// Creates an array from as many of the remaining octets as fit.
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize, L> arbitrary::Arbitrary<'a> for Array<N, L>
+where L: ArrayLen {
+    fn arbitrary(
+        u: &mut arbitrary::Unstructured<'a>
+    ) -> arbitrary::Result<Self> {
+        let len = u.arbitrary_len::<u8>()?.min(N);
+        let mut res = Self::new();
+        res.octets[..len].copy_from_slice(u.bytes(len)?);
+        res.set_len(len);
+        Ok(res)
     }
 
-    fn deserialize_with_visitor<D, V>(
-        deserializer: D,
-        visitor: V,
-    ) -> Result<V::Value, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-        V: serde::de::Visitor<'de>,
-    {
-        deserializer.deserialize_byte_buf(visitor)
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, Some(N + core::mem::size_of::<u32>()))
     }
+}
 
-    fn visitor() -> Self::Visitor {
-        ArrayVisitor
+#[cfg(feature = "proptest")]
+impl<const N: usize, L> proptest::arbitrary::Arbitrary for Array<N, L>
+where L: ArrayLen {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        proptest::collection::VecStrategy<proptest::num::u8::Any>,
+        fn(std::vec::Vec<u8>) -> Self
+    >;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        proptest::collection::vec(proptest::num::u8::ANY, 0..=N).prop_map(
+            |vec| Self::try_from(vec.as_slice()).expect("vec too long")
+        )
     }
 }
 
 
+//------------ ArrayLen ------------------------------------------------------
+
+/// A type that can be used to store the length of an [`Array`].
+///
+/// The trait is implemented for `u8`, `u16`, `u32`, and `usize`. It is
+/// sealed and cannot be implemented outside of this crate.
+pub trait ArrayLen: Copy + Default + sealed::Sealed + 'static {
+    /// The largest length that can be stored in a value of the type.
+    const MAX: usize;
+
+    /// The value representing a length of zero.
+    const ZERO: Self;
+
+    /// Converts the stored length into a `usize`.
+    fn to_usize(self) -> usize;
+
+    /// Converts a `usize` into a stored length.
+    ///
+    /// The caller must ensure that `len` is not greater than `Self::MAX`.
+    fn from_usize(len: usize) -> Self;
+}
+
+macro_rules! array_len {
+    ( $( $type:ident ),* ) => {
+        $(
+            impl sealed::Sealed for $type { }
+
+            impl ArrayLen for $type {
+                const MAX: usize = if $type::BITS >= usize::BITS {
+                    usize::MAX
+                }
+                else {
+                    $type::MAX as usize
+                };
+
+                const ZERO: Self = 0;
+
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+
+                fn from_usize(len: usize) -> Self {
+                    len as $type
+                }
+            }
+        )*
+    }
+}
+
+array_len!(u8, u16, u32, usize);
+
+mod sealed {
+    pub trait Sealed { }
+}
+
+
+//------------ array! -------------------------------------------------------
+
+/// Creates an [`Array`] from a byte string literal in const context.
+///
+/// The macro can be used in two forms. The first, `array!(b"...")`, creates
+/// an `Array<N>` where `N` is the length of the literal. The second,
+/// `array!(N; b"...")`, creates an `Array<N>` with the given capacity.
+/// In the second form, a literal that is too long results in a compile
+/// time error.
+///
+/// Instead of a literal, any constant expression of type `&[u8]` can be
+/// used.
+///
+/// The macro always creates arrays with the default length type `usize`.
+/// Arrays with other length types can’t be created in const context.
+///
+/// ```
+/// use octseq::{array, Array};
+///
+/// static PREFIX: Array<8> = array!(8; b"\x20\x01\x0d\xb8");
+/// let exact = array!(b"abc");
+/// assert_eq!(PREFIX.as_slice(), b"\x20\x01\x0d\xb8");
+/// assert_eq!(exact.capacity(), 3);
+/// ```
+#[macro_export]
+macro_rules! array {
+    ( $len:expr; $octets:expr ) => {
+        {
+            const ARRAY: $crate::Array<{ $len }>
+                = $crate::Array::from_slice_const($octets);
+            ARRAY
+        }
+    };
+    ( $octets:expr ) => {
+        {
+            const OCTETS: &[u8] = $octets;
+            const ARRAY: $crate::Array<{ OCTETS.len() }>
+                = $crate::Array::from_slice_const(OCTETS);
+            ARRAY
+        }
+    };
+}
+
+
 //------------ ArrayVisitor ----------------------------------------------
 
 #[cfg(feature = "serde")]
@@ -303,6 +904,7 @@ pub struct ArrayVisitor<const N: usize>;
 
 #[cfg(feature = "serde")]
 impl<const N: usize> ArrayVisitor<N> {
+    /// Deserializes an array using this visitor.
     pub fn deserialize<'de, D: serde::Deserializer<'de>>(
         self,
         deserializer: D,
@@ -319,10 +921,278 @@ impl<'de, const N: usize> serde::de::Visitor<'de> for ArrayVisitor<N> {
         f.write_str("an octet sequence")
     }
 
+    fn visit_bytes<E: serde::de::Error>(
+        self, value: &[u8]
+    ) -> Result<Self::Value, E> {
+        CompactArrayVisitor::new().visit_bytes(value)
+    }
+
+    fn visit_str<E: serde::de::Error>(
+        self, value: &str
+    ) -> Result<Self::Value, E> {
+        CompactArrayVisitor::new().visit_str(value)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self, seq: A
+    ) -> Result<Self::Value, A::Error> {
+        CompactArrayVisitor::new().visit_seq(seq)
+    }
+}
+
+
+//------------ CompactArrayVisitor -------------------------------------------
+
+/// The visitor for arrays with a length type other than `usize`.
+#[cfg(feature = "serde")]
+pub struct CompactArrayVisitor<const N: usize, L>(PhantomData<L>);
+
+#[cfg(feature = "serde")]
+impl<const N: usize, L: ArrayLen> CompactArrayVisitor<N, L> {
+    /// Creates a new visitor.
+    pub fn new() -> Self {
+        CompactArrayVisitor(PhantomData)
+    }
+
+    /// Deserializes an array using this visitor.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Array<N, L>, D::Error> {
+        deserializer.deserialize_byte_buf(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize, L: ArrayLen> Default for CompactArrayVisitor<N, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize, L> serde::de::Visitor<'de>
+for CompactArrayVisitor<N, L>
+where L: ArrayLen {
+    type Value = Array<N, L>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an octet sequence")
+    }
+
     fn visit_bytes<E: serde::de::Error>(
         self, value: &[u8]
     ) -> Result<Self::Value, E> {
         Array::try_from(value).map_err(E::custom)
     }
+
+    fn visit_str<E: serde::de::Error>(
+        self, value: &str
+    ) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self, mut seq: A
+    ) -> Result<Self::Value, A::Error> {
+        let mut res = Array::new();
+        while let Some(octet) = seq.next_element()? {
+            if res.push(octet).is_err() {
+                return Err(serde::de::Error::custom(ShortBuf))
+            }
+        }
+        Ok(res)
+    }
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn size() {
+        use core::mem::size_of;
+
+        assert_eq!(size_of::<Array<16, u8>>(), 17);
+        assert_eq!(size_of::<Array<255, u8>>(), 256);
+        assert_eq!(size_of::<Array<16, u16>>(), 18);
+        assert_eq!(size_of::<Array<65535, u16>>(), 65538);
+        assert_eq!(size_of::<Array<16, u32>>(), 20);
+        assert_eq!(size_of::<Array<16>>(), 16 + size_of::<usize>());
+    }
+
+    #[test]
+    fn compact_len() {
+        let mut array = Array::<255, u8>::new();
+        array.resize(255, b'x').unwrap();
+        assert_eq!(array.len(), 255);
+        assert_eq!(array.push(0), Err(ShortBuf));
+        array.truncate(3);
+        assert_eq!(array, b"xxx");
+        assert_eq!(
+            Array::<4, u8>::try_from(b"12345".as_ref()).map(|_| ()),
+            Err(ShortBuf)
+        );
+    }
+
+    #[test]
+    fn const_construction() {
+        const FROM_ARRAY: Array<4> = Array::from_array([1, 2]);
+        static FROM_SLICE: Array<4> = Array::from_slice_const(b"abc");
+        const EMPTY: Array<4, u8> = Array::new();
+
+        assert_eq!(FROM_ARRAY, b"\x01\x02");
+        assert_eq!(FROM_SLICE, b"abc");
+        assert!(EMPTY.is_empty());
+        assert_eq!(array!(b"abcd"), b"abcd");
+        assert_eq!(array!(b"abcd").capacity(), 4);
+        assert_eq!(array!(16; b"abcd"), b"abcd");
+        assert_eq!(array!(16; b"abcd").capacity(), 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_slice_const_overflow() {
+        let slice: &[u8] = b"abcde";
+        let _ = Array::<4>::from_slice_const(slice);
+    }
+
+    #[test]
+    fn conversions() {
+        let array: Array<8, u8> = [1, 2, 3].into();
+        assert_eq!(array, b"\x01\x02\x03");
+        assert_eq!(<[u8; 3]>::try_from(array.clone()), Ok([1, 2, 3]));
+        assert_eq!(<[u8; 4]>::try_from(array.clone()), Err(array.clone()));
+        assert_eq!(<[u8; 2]>::try_from(array.clone()), Err(array.clone()));
+
+        #[cfg(feature = "heapless")]
+        {
+            let vec = heapless::Vec::<u8, 8>::from(array.clone());
+            assert_eq!(vec.as_slice(), b"\x01\x02\x03");
+            assert_eq!(Array::<8>::from(vec), b"\x01\x02\x03");
+        }
+
+        #[cfg(feature = "smallvec")]
+        {
+            let vec = smallvec::SmallVec::from(array.clone());
+            assert!(!vec.spilled());
+            assert_eq!(vec.as_slice(), b"\x01\x02\x03");
+            assert_eq!(Array::<3>::try_from(vec.clone()).unwrap(), vec);
+            assert_eq!(Array::<2>::try_from(vec.clone()), Err(vec));
+        }
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut array = Array::<8>::from([1, 2, 3]);
+        for item in &mut array {
+            *item += 1
+        }
+        assert!((&array).into_iter().eq([2, 3, 4].iter()));
+        assert!(array.into_iter().rev().eq([4, 3, 2]));
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut array = Array::<2>::new();
+        assert_eq!(array.pop(), None);
+        assert_eq!(array.push(1), Ok(()));
+        assert_eq!(array.push(2), Ok(()));
+        assert!(array.is_full());
+        assert_eq!(array.push(3), Err(ShortBuf));
+        assert_eq!(array.pop(), Some(2));
+        assert_eq!(array, b"\x01");
+    }
+
+    #[test]
+    fn insert_remove_splice() {
+        let mut array = Array::<8>::try_from(b"0123".as_ref()).unwrap();
+        assert_eq!(array.insert(1, b"ab"), Ok(()));
+        assert_eq!(array, b"0ab123");
+        assert_eq!(array.insert(6, b"cd"), Ok(()));
+        assert_eq!(array, b"0ab123cd");
+        assert_eq!(array.insert(0, b"x"), Err(ShortBuf));
+        array.remove(1..3);
+        assert_eq!(array, b"0123cd");
+        array.remove(4..);
+        assert_eq!(array, b"0123");
+        assert_eq!(array.splice(1..3, b"xyz"), Ok(()));
+        assert_eq!(array, b"0xyz3");
+        assert_eq!(array.splice(..4, b"-"), Ok(()));
+        assert_eq!(array, b"-3");
+        assert_eq!(array.splice(.., b"123456789"), Err(ShortBuf));
+        assert_eq!(array, b"-3");
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut array = Array::<8>::try_from(b"0123".as_ref()).unwrap();
+        array.remove(2..5);
+    }
+
+    #[test]
+    fn extend_retain_resize_fill() {
+        let mut array = Array::<8>::try_from(b"0123".as_ref()).unwrap();
+        assert_eq!(array.extend_from_within(1..3), Ok(()));
+        assert_eq!(array, b"012312");
+        assert_eq!(array.extend_from_within(..3), Err(ShortBuf));
+        array.retain(|&ch| ch != b'1');
+        assert_eq!(array, b"0232");
+        assert_eq!(array.resize(6, b'x'), Ok(()));
+        assert_eq!(array, b"0232xx");
+        assert_eq!(array.resize(9, b'x'), Err(ShortBuf));
+        assert_eq!(array.resize(2, b'x'), Ok(()));
+        assert_eq!(array, b"02");
+        array.fill(b'-');
+        assert_eq!(array, b"--");
+        array.clear();
+        assert!(array.is_empty());
+        assert_eq!(array.capacity(), 8);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn visitors() {
+        use serde::de::value::{BytesDeserializer, Error};
+
+        let array = ArrayVisitor::<4>.deserialize(
+            BytesDeserializer::<Error>::new(b"ab")
+        ).unwrap();
+        assert_eq!(array, b"ab");
+
+        let array = CompactArrayVisitor::<4, u8>::new().deserialize(
+            BytesDeserializer::<Error>::new(b"ab")
+        ).unwrap();
+        assert_eq!(array, b"ab");
+        assert!(
+            CompactArrayVisitor::<1, u8>::default().deserialize(
+                BytesDeserializer::<Error>::new(b"ab")
+            ).is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize() {
+        use zeroize::Zeroize;
+
+        let mut array = Array::<8>::try_from(b"secret".as_ref()).unwrap();
+        array.truncate(2);
+        assert_eq!(array, b"se");
+        array.resize_raw(6).unwrap();
+        assert_eq!(array, b"se\0\0\0\0");
+        array.resize_raw(1).unwrap();
+        array.resize_raw(8).unwrap();
+        assert_eq!(array, b"s\0\0\0\0\0\0\0");
+
+        let mut array = Array::<8>::try_from(b"secret".as_ref()).unwrap();
+        array.zeroize();
+        assert!(array.is_empty());
+        array.resize_raw(8).unwrap();
+        assert_eq!(array, [0u8; 8]);
+    }
+}
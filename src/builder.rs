@@ -60,7 +60,7 @@ pub trait OctetsBuilder {
     ) -> Result<(), Self::AppendError>;
 }
 
-impl<'a, T: OctetsBuilder> OctetsBuilder for &'a mut T {
+impl<T: OctetsBuilder> OctetsBuilder for &mut T {
     type AppendError = T::AppendError;
 
     fn append_slice(
@@ -148,13 +148,13 @@ pub trait Truncate {
     fn truncate(&mut self, len: usize);
 }
 
-impl<'a, T: Truncate> Truncate for &'a mut T {
+impl<T: Truncate> Truncate for &mut T {
     fn truncate(&mut self, len: usize) {
         (*self).truncate(len)
     }
 }
 
-impl<'a> Truncate for &'a [u8] {
+impl Truncate for &[u8] {
     fn truncate(&mut self, len: usize) {
         if len < self.len() {
             *self = &self[..len]
@@ -350,7 +350,7 @@ impl IntoBuilder for Vec<u8> {
 }
 
 #[cfg(feature = "alloc")]
-impl<'a> IntoBuilder for &'a [u8] {
+impl IntoBuilder for &[u8] {
     type Builder = Vec<u8>;
 
     fn into_builder(self) -> Self::Builder {
@@ -465,6 +465,392 @@ pub type BuilderAppendError<Octets>
     = <<Octets as FromBuilder>::Builder as OctetsBuilder>::AppendError;
 
 
+//------------ Limited -------------------------------------------------------
+
+/// An octets builder that limits the size of another builder.
+///
+/// The type wraps an octets builder and makes sure that the total length
+/// of the assembled octets doesn’t exceed a given limit. Appending data
+/// that would take the length beyond the limit fails with [`ShortBuf`],
+/// leaving the builder untouched. This allows using a growable builder
+/// such as `Vec<u8>` to assemble a message that has to fit into a given
+/// size.
+///
+/// The limit can be changed at any time. In particular, it can be
+/// temporarily reduced via [`shrink_limit`][Self::shrink_limit] in order
+/// to keep space available for data that needs to be appended at the very
+/// end, such as a signature, and increased again via
+/// [`grow_limit`][Self::grow_limit] before appending this data.
+#[derive(Clone, Debug)]
+pub struct Limited<Builder> {
+    /// The wrapped builder.
+    builder: Builder,
+
+    /// The maximum length of the builder’s content.
+    limit: usize,
+}
+
+impl<Builder> Limited<Builder> {
+    /// Creates a new limited builder from a builder and the limit.
+    ///
+    /// If the builder already contains more than `limit` octets, appending
+    /// will fail until it is truncated to a length within the limit.
+    pub fn new(builder: Builder, limit: usize) -> Self {
+        Limited { builder, limit }
+    }
+
+    /// Returns a reference to the wrapped builder.
+    pub fn as_builder(&self) -> &Builder {
+        &self.builder
+    }
+
+    /// Converts the limited builder into the wrapped builder.
+    pub fn into_builder(self) -> Builder {
+        self.builder
+    }
+
+    /// Returns the current limit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Sets the limit to a new value.
+    ///
+    /// The new limit may be smaller than the length of the already
+    /// assembled octets. In this case, appending fails until the builder
+    /// is truncated to a length within the limit.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit
+    }
+
+    /// Reduces the limit by `len` octets.
+    ///
+    /// If the new limit would be smaller than the length of the already
+    /// assembled octets, returns an error and leaves the limit unchanged.
+    pub fn shrink_limit(&mut self, len: usize) -> Result<(), ShortBuf>
+    where Builder: AsRef<[u8]> {
+        if len > self.remaining() {
+            return Err(ShortBuf)
+        }
+        self.limit -= len;
+        Ok(())
+    }
+
+    /// Increases the limit by `len` octets.
+    ///
+    /// The limit will be capped at `usize::MAX`.
+    pub fn grow_limit(&mut self, len: usize) {
+        self.limit = self.limit.saturating_add(len)
+    }
+
+    /// Returns the number of octets that can still be appended.
+    pub fn remaining(&self) -> usize
+    where Builder: AsRef<[u8]> {
+        self.limit.saturating_sub(self.builder.as_ref().len())
+    }
+}
+
+
+//--- OctetsBuilder, Truncate, and FreezeBuilder
+
+impl<Builder> OctetsBuilder for Limited<Builder>
+where Builder: OctetsBuilder + AsRef<[u8]> {
+    type AppendError = ShortBuf;
+
+    fn append_slice(&mut self, slice: &[u8]) -> Result<(), ShortBuf> {
+        if slice.len() > self.remaining() {
+            return Err(ShortBuf)
+        }
+        self.builder.append_slice(slice).map_err(Into::into)
+    }
+}
+
+impl<Builder: Truncate> Truncate for Limited<Builder> {
+    fn truncate(&mut self, len: usize) {
+        self.builder.truncate(len)
+    }
+}
+
+impl<Builder: FreezeBuilder> FreezeBuilder for Limited<Builder> {
+    type Octets = Builder::Octets;
+
+    fn freeze(self) -> Self::Octets {
+        self.builder.freeze()
+    }
+}
+
+
+//--- AsRef and AsMut
+
+impl<Builder: AsRef<[u8]>> AsRef<[u8]> for Limited<Builder> {
+    fn as_ref(&self) -> &[u8] {
+        self.builder.as_ref()
+    }
+}
+
+impl<Builder: AsMut<[u8]>> AsMut<[u8]> for Limited<Builder> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.builder.as_mut()
+    }
+}
+
+
+//------------ Counter -------------------------------------------------------
+
+/// An octets builder that only counts the octets appended to it.
+///
+/// The builder doesn’t store any data. It only keeps track of the length
+/// of the octets sequence it would have assembled. Appending to it never
+/// fails. This allows running generic code that composes data into an
+/// octets builder once to determine the length of the result, e.g., for a
+/// length prefix or to size a buffer, and then again to actually write it.
+///
+/// The length saturates at `usize::MAX` instead of overflowing, so even
+/// appending the same large slice over and over again never panics.
+///
+/// Because there is no data, the type doesn’t implement `AsRef<[u8]>`.
+/// Code that needs to look at the octets already assembled can’t be used
+/// with it.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Counter {
+    /// The number of octets appended.
+    len: usize,
+}
+
+impl Counter {
+    /// Creates a new counter with a length of zero.
+    pub const fn new() -> Self {
+        Counter { len: 0 }
+    }
+
+    /// Returns the number of octets appended so far.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no octets have been appended so far.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+
+//--- OctetsBuilder, Truncate, and EmptyBuilder
+
+impl OctetsBuilder for Counter {
+    type AppendError = Infallible;
+
+    fn append_slice(&mut self, slice: &[u8]) -> Result<(), Infallible> {
+        self.len = self.len.saturating_add(slice.len());
+        Ok(())
+    }
+}
+
+impl Truncate for Counter {
+    fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len)
+    }
+}
+
+impl EmptyBuilder for Counter {
+    fn empty() -> Self {
+        Self::new()
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Self::new()
+    }
+}
+
+
+//------------ Headroom ------------------------------------------------------
+
+/// An octets builder that allows prepending data.
+///
+/// The type wraps another octets builder and keeps a configurable amount
+/// of space at its beginning free. Data is appended to the end as usual
+/// via the [`OctetsBuilder`] trait. In addition, the method
+/// [`prepend_slice`][Self::prepend_slice] places data in front of the
+/// already assembled octets, using up the headroom. This is useful when
+/// a message needs to be wrapped in some outer header or prefixed with its
+/// length once it has been assembled.
+///
+/// When freezing a `Headroom<BytesMut>`, the remaining headroom is split
+/// off without copying. When freezing a `Headroom<Vec<u8>>`, the content
+/// has to be moved to the start of the vec unless the headroom has been
+/// used up completely. With the `bytes` feature enabled, the method
+/// `freeze_bytes` turns a `Headroom<Vec<u8>>` into `Bytes` instead,
+/// skipping the headroom without moving the content.
+#[derive(Clone, Debug)]
+pub struct Headroom<Builder> {
+    /// The wrapped builder.
+    ///
+    /// The first `start` octets are the unused headroom.
+    builder: Builder,
+
+    /// The index of the first octet of actual content in the builder.
+    start: usize,
+}
+
+impl<Builder> Headroom<Builder> {
+    /// Creates a new, empty builder with the given headroom.
+    ///
+    /// The headroom is created by appending `headroom` zero octets to a new
+    /// empty builder. If this fails, returns an error.
+    pub fn try_new(headroom: usize) -> Result<Self, Builder::AppendError>
+    where Builder: OctetsBuilder + EmptyBuilder {
+        Self::append_headroom(Builder::empty(), headroom)
+    }
+
+    /// Creates a new, empty builder with the given headroom.
+    pub fn new(headroom: usize) -> Self
+    where
+        Builder: OctetsBuilder + EmptyBuilder,
+        Builder::AppendError: Into<Infallible>,
+    {
+        infallible(Self::try_new(headroom))
+    }
+
+    /// Creates a new builder with headroom atop an existing builder.
+    ///
+    /// Any octets the builder already contains are discarded. If appending
+    /// the headroom fails, returns an error.
+    pub fn try_from_builder(
+        mut builder: Builder, headroom: usize
+    ) -> Result<Self, Builder::AppendError>
+    where Builder: OctetsBuilder + Truncate {
+        builder.truncate(0);
+        Self::append_headroom(builder, headroom)
+    }
+
+    /// Appends `headroom` zero octets to an empty builder.
+    fn append_headroom(
+        mut builder: Builder, headroom: usize
+    ) -> Result<Self, Builder::AppendError>
+    where Builder: OctetsBuilder {
+        const ZEROS: [u8; 64] = [0; 64];
+
+        let mut remaining = headroom;
+        while remaining > 0 {
+            let len = remaining.min(ZEROS.len());
+            builder.append_slice(&ZEROS[..len])?;
+            remaining -= len;
+        }
+        Ok(Headroom { builder, start: headroom })
+    }
+
+    /// Returns the amount of headroom still available.
+    pub fn headroom(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the assembled octets as a slice.
+    pub fn as_slice(&self) -> &[u8]
+    where Builder: AsRef<[u8]> {
+        &self.builder.as_ref()[self.start..]
+    }
+
+    /// Returns the assembled octets as a mutable slice.
+    pub fn as_slice_mut(&mut self) -> &mut [u8]
+    where Builder: AsMut<[u8]> {
+        &mut self.builder.as_mut()[self.start..]
+    }
+
+    /// Returns the length of the assembled octets.
+    pub fn len(&self) -> usize
+    where Builder: AsRef<[u8]> {
+        self.as_slice().len()
+    }
+
+    /// Returns whether the builder is empty.
+    pub fn is_empty(&self) -> bool
+    where Builder: AsRef<[u8]> {
+        self.as_slice().is_empty()
+    }
+
+    /// Places the content of a slice in front of the assembled octets.
+    ///
+    /// If the slice is longer than the remaining headroom, returns an error
+    /// and leaves the builder untouched.
+    pub fn prepend_slice(&mut self, slice: &[u8]) -> Result<(), ShortBuf>
+    where Builder: AsMut<[u8]> {
+        let start = self.start.checked_sub(slice.len()).ok_or(ShortBuf)?;
+        self.builder.as_mut()[start..self.start].copy_from_slice(slice);
+        self.start = start;
+        Ok(())
+    }
+}
+
+
+#[cfg(all(feature = "alloc", feature = "bytes"))]
+impl Headroom<Vec<u8>> {
+    /// Converts the builder into a `Bytes` value.
+    ///
+    /// Unlike freezing the builder into a `Vec<u8>`, this doesn’t move the
+    /// assembled octets to the start of the vec but merely skips the
+    /// remaining headroom.
+    pub fn freeze_bytes(self) -> Bytes {
+        let mut res = Bytes::from(self.builder);
+        bytes::Buf::advance(&mut res, self.start);
+        res
+    }
+}
+
+
+//--- OctetsBuilder, Truncate, and FreezeBuilder
+
+impl<Builder: OctetsBuilder> OctetsBuilder for Headroom<Builder> {
+    type AppendError = Builder::AppendError;
+
+    fn append_slice(
+        &mut self, slice: &[u8]
+    ) -> Result<(), Self::AppendError> {
+        self.builder.append_slice(slice)
+    }
+}
+
+impl<Builder: Truncate> Truncate for Headroom<Builder> {
+    fn truncate(&mut self, len: usize) {
+        self.builder.truncate(self.start.saturating_add(len))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FreezeBuilder for Headroom<Vec<u8>> {
+    type Octets = Vec<u8>;
+
+    fn freeze(mut self) -> Self::Octets {
+        self.builder.drain(..self.start);
+        self.builder
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl FreezeBuilder for Headroom<BytesMut> {
+    type Octets = Bytes;
+
+    fn freeze(mut self) -> Self::Octets {
+        let _ = self.builder.split_to(self.start);
+        self.builder.freeze()
+    }
+}
+
+
+//--- AsRef and AsMut
+
+impl<Builder: AsRef<[u8]>> AsRef<[u8]> for Headroom<Builder> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<Builder: AsMut<[u8]>> AsMut<[u8]> for Headroom<Builder> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_slice_mut()
+    }
+}
+
+
 //============ Error Handling ================================================
 
 //------------ ShortBuf ------------------------------------------------------
@@ -530,3 +916,154 @@ where
     infallible(op())
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn limited() {
+        let mut builder = Limited::new(Vec::new(), 8);
+        builder.append_slice(b"1234").unwrap();
+        assert_eq!(builder.remaining(), 4);
+        assert_eq!(builder.append_slice(b"56789"), Err(ShortBuf));
+        assert_eq!(builder.as_ref(), b"1234");
+
+        // Reserve space for a trailer.
+        builder.shrink_limit(2).unwrap();
+        assert_eq!(builder.append_slice(b"567"), Err(ShortBuf));
+        builder.append_slice(b"56").unwrap();
+        assert_eq!(builder.shrink_limit(1), Err(ShortBuf));
+        assert_eq!(builder.limit(), 6);
+        builder.grow_limit(2);
+        builder.append_slice(b"ab").unwrap();
+        assert_eq!(builder.remaining(), 0);
+        assert_eq!(builder.append_slice(b""), Ok(()));
+
+        // A limit below the current length blocks appending until
+        // truncated.
+        builder.set_limit(4);
+        assert_eq!(builder.remaining(), 0);
+        assert_eq!(builder.append_slice(b"x"), Err(ShortBuf));
+        builder.truncate(3);
+        builder.append_slice(b"x").unwrap();
+        assert_eq!(builder.freeze(), b"123x");
+    }
+
+    #[test]
+    fn limited_short_inner() {
+        let mut builder = Limited::new(crate::array::Array::<4>::new(), 8);
+        assert_eq!(builder.remaining(), 8);
+        assert_eq!(builder.append_slice(b"12345"), Err(ShortBuf));
+        builder.append_slice(b"1234").unwrap();
+        assert_eq!(builder.as_builder().as_slice(), b"1234");
+    }
+
+    #[test]
+    fn counter() {
+        fn compose<Target: OctetsBuilder>(
+            target: &mut Target
+        ) -> Result<(), Target::AppendError> {
+            target.append_slice(b"foo")?;
+            target.append_slice(&[0; 20])
+        }
+
+        let mut counter = Counter::new();
+        assert!(counter.is_empty());
+        infallible(compose(&mut counter));
+        assert_eq!(counter.len(), 23);
+        counter.truncate(30);
+        assert_eq!(counter.len(), 23);
+        counter.truncate(3);
+        assert_eq!(counter.len(), 3);
+
+        // Measure first, then write.
+        let mut counter = Counter::new();
+        infallible(compose(&mut counter));
+        let mut array = crate::array::Array::<23>::new();
+        compose(&mut array).unwrap();
+        assert_eq!(array.len(), counter.len());
+
+        // The length saturates instead of overflowing.
+        let mut counter = Counter { len: usize::MAX - 8 };
+        infallible(counter.append_slice(&[0; 16]));
+        assert_eq!(counter.len(), usize::MAX);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn headroom() {
+        let mut builder = Headroom::<Vec<u8>>::new(100);
+        assert_eq!(builder.headroom(), 100);
+        assert!(builder.is_empty());
+        builder.append_slice(b"payload").unwrap();
+        builder.prepend_slice(b"head").unwrap();
+        builder.prepend_slice(&[0; 96]).unwrap();
+        assert_eq!(builder.prepend_slice(b"x"), Err(ShortBuf));
+        assert_eq!(builder.len(), 107);
+        builder.truncate(103);
+        assert_eq!(&builder.as_ref()[96..], b"headpay");
+
+        let vec = builder.freeze();
+        assert_eq!(vec.len(), 103);
+        assert_eq!(&vec[96..], b"headpay");
+
+        // Freezing with headroom left.
+        let mut builder = Headroom::<Vec<u8>>::new(4);
+        builder.append_slice(b"payload").unwrap();
+        builder.prepend_slice(&[7]).unwrap();
+        assert_eq!(builder.freeze(), b"\x07payload");
+
+        // Existing content of the builder is discarded.
+        let mut builder = Headroom::try_from_builder(
+            Vec::from(b"abc".as_ref()), 2
+        ).unwrap();
+        assert_eq!(builder.headroom(), 2);
+        assert!(builder.is_empty());
+        builder.append_slice(b"payload").unwrap();
+        builder.prepend_slice(b"ab").unwrap();
+        assert_eq!(builder.freeze(), b"abpayload");
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn headroom_bytes() {
+        let mut builder = Headroom::<BytesMut>::new(2);
+        builder.append_slice(b"payload").unwrap();
+        builder.prepend_slice(&7u16.to_be_bytes()).unwrap();
+        assert_eq!(builder.headroom(), 0);
+        assert_eq!(builder.freeze(), b"\x00\x07payload".as_ref());
+
+        let mut builder = Headroom::<BytesMut>::new(2);
+        builder.append_slice(b"payload").unwrap();
+        builder.prepend_slice(b"!").unwrap();
+        assert_eq!(builder.freeze(), b"!payload".as_ref());
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "bytes"))]
+    fn headroom_freeze_bytes() {
+        let mut builder = Headroom::<Vec<u8>>::new(4);
+        builder.append_slice(b"payload").unwrap();
+        builder.prepend_slice(b"!").unwrap();
+        let ptr = builder.as_slice().as_ptr();
+        let bytes = builder.freeze_bytes();
+        assert_eq!(bytes, b"!payload".as_ref());
+        assert_eq!(bytes.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn headroom_short_buf() {
+        use crate::array::Array;
+
+        assert!(Headroom::<Array<4>>::try_new(5).is_err());
+        let mut builder = Headroom::<Array<4>>::try_new(2).unwrap();
+        builder.append_slice(b"12").unwrap();
+        assert_eq!(builder.append_slice(b"3"), Err(ShortBuf));
+        builder.prepend_slice(b"ab").unwrap();
+        assert_eq!(builder.as_slice(), b"ab12");
+    }
+}
@@ -0,0 +1,702 @@
+//! Textual encodings of octets sequences.
+//!
+//! This module provides adaptors that implement [`Display`][fmt::Display]
+//! for any octets sequence using one of the common textual encodings as
+//! well as functions to decode such text back into an octets builder.
+//!
+//! The supported encodings are
+//!
+//! * hexadecimal digits, a.k.a. base16, via [`hex`] and [`decode_hex`],
+//! * base64 using the standard alphabet via [`base64`] and
+//!   [`decode_base64`],
+//! * base64 using the URL and filename safe alphabet via [`base64_url`]
+//!   and [`decode_base64_url`], and
+//! * base32 using the ‘extended hex’ alphabet as used by NSEC3 in DNS
+//!   via [`base32hex`] and [`decode_base32hex`].
+//!
+//! All encodings are defined in [RFC 4648]. In addition, [`hexdump`]
+//! displays octets in the multi-line format used by the `hexdump -C`
+//! command which is helpful for debugging. The module does not need the
+//! standard library.
+//!
+//! [RFC 4648]: https://tools.ietf.org/html/rfc4648
+
+use core::fmt;
+use crate::builder::{OctetsBuilder, ShortBuf};
+
+
+//------------ Hex -----------------------------------------------------------
+
+/// Returns a value displaying the octets as hexadecimal digits.
+///
+/// By default, the value displays lowercase digits without any separators.
+/// This can be changed via the methods of the returned [`Hex`] value.
+pub fn hex<Octs: AsRef<[u8]> + ?Sized>(octets: &Octs) -> Hex<'_> {
+    Hex { octets: octets.as_ref(), upper: false, separator: "" }
+}
+
+/// A value displaying octets as hexadecimal digits.
+///
+/// A value of this type is created via the [`hex`] function.
+#[derive(Clone, Copy)]
+pub struct Hex<'a> {
+    /// The octets to display.
+    octets: &'a [u8],
+
+    /// Whether to display uppercase digits.
+    upper: bool,
+
+    /// A string to be placed between each octet.
+    separator: &'a str,
+}
+
+impl<'a> Hex<'a> {
+    /// Changes the value to display uppercase digits.
+    pub fn upper(self) -> Self {
+        Hex { upper: true, ..self }
+    }
+
+    /// Changes the value to display lowercase digits.
+    pub fn lower(self) -> Self {
+        Hex { upper: false, ..self }
+    }
+
+    /// Changes the value to place `separator` between each octet.
+    pub fn with_separator(self, separator: &'a str) -> Self {
+        Hex { separator, ..self }
+    }
+}
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = if self.upper { HEX_UPPER } else { HEX_LOWER };
+        for (idx, &octet) in self.octets.iter().enumerate() {
+            if idx > 0 && !self.separator.is_empty() {
+                f.write_str(self.separator)?;
+            }
+            let buf = [
+                alphabet[usize::from(octet >> 4)],
+                alphabet[usize::from(octet & 0x0F)],
+            ];
+            f.write_str(ascii_str(&buf))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Decodes hexadecimal digits and appends the octets to a builder.
+///
+/// Both uppercase and lowercase digits are accepted. The input must
+/// consist of an even number of digits and nothing else.
+///
+/// If the input contains invalid data, returns an error and leaves the
+/// builder with whatever octets had been decoded until then.
+pub fn decode_hex<Target: OctetsBuilder>(
+    input: impl AsRef<[u8]>, target: &mut Target
+) -> Result<(), DecodeError> {
+    let input = input.as_ref();
+    let mut chunks = input.chunks_exact(2);
+    for (idx, chunk) in (&mut chunks).enumerate() {
+        let pos = idx * 2;
+        let high = hex_digit(chunk[0]).ok_or(DecodeError::Invalid(pos))?;
+        let low = hex_digit(chunk[1]).ok_or(DecodeError::Invalid(pos + 1))?;
+        append(target, &[high << 4 | low])?;
+    }
+    if !chunks.remainder().is_empty() {
+        return Err(DecodeError::Invalid(input.len()))
+    }
+    Ok(())
+}
+
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+fn hex_digit(ch: u8) -> Option<u8> {
+    match ch {
+        b'0'..=b'9' => Some(ch - b'0'),
+        b'a'..=b'f' => Some(ch - b'a' + 10),
+        b'A'..=b'F' => Some(ch - b'A' + 10),
+        _ => None
+    }
+}
+
+
+//------------ Base64 --------------------------------------------------------
+
+/// Returns a value displaying the octets in base64 encoding.
+///
+/// The value uses the standard alphabet and adds padding. The latter can
+/// be changed via [`Base64::without_padding`].
+pub fn base64<Octs: AsRef<[u8]> + ?Sized>(octets: &Octs) -> Base64<'_> {
+    Base64 { octets: octets.as_ref(), alphabet: BASE64_STD, pad: true }
+}
+
+/// Returns a value displaying the octets in URL-safe base64 encoding.
+///
+/// The value uses the URL and filename safe alphabet and adds padding.
+/// The latter can be changed via [`Base64::without_padding`].
+pub fn base64_url<Octs: AsRef<[u8]> + ?Sized>(
+    octets: &Octs
+) -> Base64<'_> {
+    Base64 { octets: octets.as_ref(), alphabet: BASE64_URL, pad: true }
+}
+
+/// A value displaying octets in base64 encoding.
+///
+/// A value of this type is created via the [`base64`] and [`base64_url`]
+/// functions.
+#[derive(Clone, Copy)]
+pub struct Base64<'a> {
+    /// The octets to display.
+    octets: &'a [u8],
+
+    /// The alphabet to use.
+    alphabet: &'static [u8; 64],
+
+    /// Whether to add padding at the end.
+    pad: bool,
+}
+
+impl Base64<'_> {
+    /// Changes the value to not add padding at the end.
+    pub fn without_padding(self) -> Self {
+        Base64 { pad: false, ..self }
+    }
+}
+
+impl fmt::Display for Base64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = self.alphabet;
+        for chunk in self.octets.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let buf = [
+                alphabet[usize::from(group[0] >> 2)],
+                alphabet[usize::from((group[0] & 0x03) << 4 | group[1] >> 4)],
+                alphabet[usize::from((group[1] & 0x0F) << 2 | group[2] >> 6)],
+                alphabet[usize::from(group[2] & 0x3F)],
+            ];
+            let len = chunk.len() + 1;
+            f.write_str(ascii_str(&buf[..len]))?;
+            if self.pad {
+                f.write_str(&"=="[..4 - len])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Base64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Decodes base64 encoded data and appends the octets to a builder.
+///
+/// The function expects the standard alphabet. Padding at the end is
+/// optional but if present must be correct. Unused bits in the last
+/// character must be zero.
+///
+/// If the input contains invalid data, returns an error and leaves the
+/// builder with whatever octets had been decoded until then.
+pub fn decode_base64<Target: OctetsBuilder>(
+    input: impl AsRef<[u8]>, target: &mut Target
+) -> Result<(), DecodeError> {
+    decode_base64_alphabet(input.as_ref(), target, BASE64_STD)
+}
+
+/// Decodes URL-safe base64 encoded data and appends it to a builder.
+///
+/// This is identical to [`decode_base64`] except that it expects the
+/// URL and filename safe alphabet.
+pub fn decode_base64_url<Target: OctetsBuilder>(
+    input: impl AsRef<[u8]>, target: &mut Target
+) -> Result<(), DecodeError> {
+    decode_base64_alphabet(input.as_ref(), target, BASE64_URL)
+}
+
+fn decode_base64_alphabet<Target: OctetsBuilder>(
+    input: &[u8], target: &mut Target, alphabet: &[u8; 64],
+) -> Result<(), DecodeError> {
+    let data = strip_padding(input, 4)?;
+    for (idx, chunk) in data.chunks(4).enumerate() {
+        let pos = idx * 4;
+        if chunk.len() == 1 {
+            return Err(DecodeError::Invalid(pos + 1))
+        }
+        let mut group = 0u32;
+        for (i, &ch) in chunk.iter().enumerate() {
+            let value = alphabet_value(alphabet, ch).ok_or(
+                DecodeError::Invalid(pos + i)
+            )?;
+            group |= u32::from(value) << (18 - 6 * i);
+        }
+        let bytes = group.to_be_bytes();
+        let len = chunk.len() - 1;
+        if bytes[1 + len..].iter().any(|&b| b != 0) {
+            return Err(DecodeError::Invalid(pos + len))
+        }
+        append(target, &bytes[1..1 + len])?;
+    }
+    Ok(())
+}
+
+const BASE64_STD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+
+//------------ Base32Hex -----------------------------------------------------
+
+/// Returns a value displaying the octets in base32hex encoding.
+///
+/// By default, the value displays lowercase characters without padding
+/// as is customary for NSEC3 hashed owner names. This can be changed via
+/// the methods of the returned [`Base32Hex`] value.
+pub fn base32hex<Octs: AsRef<[u8]> + ?Sized>(
+    octets: &Octs
+) -> Base32Hex<'_> {
+    Base32Hex { octets: octets.as_ref(), upper: false, pad: false }
+}
+
+/// A value displaying octets in base32 encoding using the hex alphabet.
+///
+/// A value of this type is created via the [`base32hex`] function.
+#[derive(Clone, Copy)]
+pub struct Base32Hex<'a> {
+    /// The octets to display.
+    octets: &'a [u8],
+
+    /// Whether to display uppercase characters.
+    upper: bool,
+
+    /// Whether to add padding at the end.
+    pad: bool,
+}
+
+impl Base32Hex<'_> {
+    /// Changes the value to display uppercase characters.
+    pub fn upper(self) -> Self {
+        Base32Hex { upper: true, ..self }
+    }
+
+    /// Changes the value to display lowercase characters.
+    pub fn lower(self) -> Self {
+        Base32Hex { upper: false, ..self }
+    }
+
+    /// Changes the value to add padding at the end.
+    pub fn with_padding(self) -> Self {
+        Base32Hex { pad: true, ..self }
+    }
+}
+
+impl fmt::Display for Base32Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = if self.upper {
+            BASE32HEX_UPPER
+        }
+        else {
+            BASE32HEX_LOWER
+        };
+        for chunk in self.octets.chunks(5) {
+            let mut group = [0u8; 8];
+            group[3..3 + chunk.len()].copy_from_slice(chunk);
+            let group = u64::from_be_bytes(group);
+            let mut buf = [0u8; 8];
+            for (i, ch) in buf.iter_mut().enumerate() {
+                *ch = alphabet[((group >> (35 - 5 * i)) & 0x1F) as usize];
+            }
+            let len = (chunk.len() * 8).div_ceil(5);
+            f.write_str(ascii_str(&buf[..len]))?;
+            if self.pad {
+                f.write_str(&"======="[..8 - len])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Base32Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Decodes base32hex encoded data and appends the octets to a builder.
+///
+/// Both uppercase and lowercase characters are accepted. Padding at the
+/// end is optional but if present must be correct. Unused bits in the
+/// last character must be zero.
+///
+/// If the input contains invalid data, returns an error and leaves the
+/// builder with whatever octets had been decoded until then.
+pub fn decode_base32hex<Target: OctetsBuilder>(
+    input: impl AsRef<[u8]>, target: &mut Target
+) -> Result<(), DecodeError> {
+    let data = strip_padding(input.as_ref(), 8)?;
+    for (idx, chunk) in data.chunks(8).enumerate() {
+        let pos = idx * 8;
+        // Only these numbers of characters can end a sequence.
+        let len = match chunk.len() {
+            2 => 1,
+            4 => 2,
+            5 => 3,
+            7 => 4,
+            8 => 5,
+            other => return Err(DecodeError::Invalid(pos + other)),
+        };
+        let mut group = 0u64;
+        for (i, &ch) in chunk.iter().enumerate() {
+            let value = alphabet_value(
+                BASE32HEX_UPPER, ch.to_ascii_uppercase()
+            ).ok_or(DecodeError::Invalid(pos + i))?;
+            group |= u64::from(value) << (35 - 5 * i);
+        }
+        let bytes = group.to_be_bytes();
+        if bytes[3 + len..].iter().any(|&b| b != 0) {
+            return Err(DecodeError::Invalid(pos + chunk.len() - 1))
+        }
+        append(target, &bytes[3..3 + len])?;
+    }
+    Ok(())
+}
+
+const BASE32HEX_LOWER: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+const BASE32HEX_UPPER: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+
+
+//------------ HexDump -------------------------------------------------------
+
+/// Returns a value displaying the octets as a hex dump.
+///
+/// The format follows the canonical output of the `hexdump -C` command:
+/// each line starts with the offset of its first octet, followed by the
+/// hex values of up to 16 octets and the printable ASCII characters of
+/// those octets. Non-printable octets are shown as a period.
+///
+/// Lines are separated by a line feed. There is no line feed after the
+/// last line.
+pub fn hexdump<Octs: AsRef<[u8]> + ?Sized>(octets: &Octs) -> HexDump<'_> {
+    HexDump { octets: octets.as_ref(), offset: 0, mark: None }
+}
+
+/// A value displaying octets as a hex dump.
+///
+/// A value of this type is created via the [`hexdump`] function.
+#[derive(Clone, Copy)]
+pub struct HexDump<'a> {
+    /// The octets to display.
+    octets: &'a [u8],
+
+    /// The offset to display for the first octet.
+    offset: usize,
+
+    /// The index of an octet to be marked.
+    mark: Option<usize>,
+}
+
+impl HexDump<'_> {
+    /// The number of octets displayed per line.
+    pub const LINE_LEN: usize = 16;
+
+    /// Changes the offset displayed for the first octet.
+    ///
+    /// This is useful if the octets are a part of some larger sequence.
+    pub fn with_offset(self, offset: usize) -> Self {
+        HexDump { offset, ..self }
+    }
+
+    /// Changes the value to mark the octet at index `mark`.
+    ///
+    /// The octet will be enclosed in square brackets. The index is
+    /// relative to the start of the displayed octets and doesn’t consider
+    /// the offset.
+    pub fn with_mark(self, mark: usize) -> Self {
+        HexDump { mark: Some(mark), ..self }
+    }
+}
+
+impl HexDump<'_> {
+    /// Writes the hex dump with every line indented by `indent`.
+    ///
+    /// If `leading_newline` is `true`, a line feed is written before the
+    /// first line, too.
+    pub(crate) fn fmt_indented(
+        &self, f: &mut fmt::Formatter, indent: &str, leading_newline: bool,
+    ) -> fmt::Result {
+        const HEX_COLS: usize = HexDump::LINE_LEN * 3 + 2;
+
+        for (line, chunk) in self.octets.chunks(Self::LINE_LEN).enumerate() {
+            let start = line * Self::LINE_LEN;
+            if line > 0 || leading_newline {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}{:08x} ", indent, self.offset + start)?;
+
+            let mut hex_buf = [b' '; HEX_COLS];
+            let mut ascii_buf = [b' '; HexDump::LINE_LEN];
+            for (i, &octet) in chunk.iter().enumerate() {
+                let col = 1 + i * 3 + usize::from(i >= Self::LINE_LEN / 2);
+                hex_buf[col] = HEX_LOWER[usize::from(octet >> 4)];
+                hex_buf[col + 1] = HEX_LOWER[usize::from(octet & 0x0F)];
+                if self.mark == Some(start + i) {
+                    hex_buf[col - 1] = b'[';
+                    hex_buf[col + 2] = b']';
+                }
+                ascii_buf[i] = if octet.is_ascii_graphic() || octet == b' ' {
+                    octet
+                }
+                else {
+                    b'.'
+                };
+            }
+            f.write_str(ascii_str(&hex_buf))?;
+            f.write_str(" |")?;
+            f.write_str(ascii_str(&ascii_buf[..chunk.len()]))?;
+            f.write_str("|")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indented(f, "", false)
+    }
+}
+
+impl fmt::Debug for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Converts a slice of ASCII characters from an alphabet into a `str`.
+fn ascii_str(slice: &[u8]) -> &str {
+    // All our alphabets are ASCII only, so this will never fail.
+    core::str::from_utf8(slice).unwrap_or_default()
+}
+
+/// Appends a slice to a builder converting the error.
+pub(crate) fn append<Target: OctetsBuilder>(
+    target: &mut Target, slice: &[u8]
+) -> Result<(), DecodeError> {
+    target.append_slice(slice).map_err(|err| err.into().into())
+}
+
+/// Returns the value of a character in the given alphabet.
+fn alphabet_value(alphabet: &[u8], ch: u8) -> Option<u8> {
+    alphabet.iter().position(|&item| item == ch).map(|pos| pos as u8)
+}
+
+/// Strips the padding from the input if present.
+///
+/// If there is padding, the input must have a multiple of `group_len`
+/// length. Returns an error pointing to the first padding character if
+/// that isn’t the case.
+fn strip_padding(
+    input: &[u8], group_len: usize
+) -> Result<&[u8], DecodeError> {
+    let data_len = input.iter().rposition(|&ch| ch != b'=').map_or(
+        0, |pos| pos + 1
+    );
+    if data_len == input.len() {
+        return Ok(input)
+    }
+    let data = &input[..data_len];
+    if !input.len().is_multiple_of(group_len)
+        || input.len() - data_len >= group_len
+        || data.contains(&b'=')
+    {
+        let pos = data.iter().position(|&ch| ch == b'=').unwrap_or(data_len);
+        return Err(DecodeError::Invalid(pos))
+    }
+    Ok(data)
+}
+
+
+//============ Error Types ===================================================
+
+//------------ DecodeError ---------------------------------------------------
+
+/// An error happened while decoding encoded data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The input contained invalid data.
+    ///
+    /// The value is the index of the offending octet in the input. If the
+    /// input ended prematurely, it is the length of the input.
+    Invalid(usize),
+
+    /// The octets builder ran out of space.
+    ShortBuf,
+}
+
+impl From<ShortBuf> for DecodeError {
+    fn from(_: ShortBuf) -> Self {
+        DecodeError::ShortBuf
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::Invalid(pos) => {
+                write!(f, "invalid encoded data at position {}", pos)
+            }
+            DecodeError::ShortBuf => fmt::Display::fmt(&ShortBuf, f)
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    // Test vectors are from RFC 4648, section 10.
+    const VECTORS: &[&[u8]] = &[
+        b"", b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"
+    ];
+
+    fn decoded(
+        decode: fn(&str, &mut Vec<u8>) -> Result<(), DecodeError>,
+        input: &str,
+    ) -> Vec<u8> {
+        let mut res = Vec::new();
+        decode(input, &mut res).unwrap();
+        res
+    }
+
+    #[test]
+    fn hex_vectors() {
+        let encoded = [
+            "", "66", "666f", "666f6f", "666f6f62", "666f6f6261",
+            "666f6f626172"
+        ];
+        for (&octets, text) in VECTORS.iter().zip(encoded) {
+            let upper = text.to_ascii_uppercase();
+            assert_eq!(hex(octets).to_string(), text);
+            assert_eq!(hex(octets).upper().to_string(), upper);
+            assert_eq!(decoded(|i, t| decode_hex(i, t), text), octets);
+            assert_eq!(decoded(|i, t| decode_hex(i, t), &upper), octets);
+        }
+        assert_eq!(
+            hex(b"\x01\xab\xff").with_separator(":").to_string(), "01:ab:ff"
+        );
+        assert_eq!(hex(b"\x01").with_separator(":").to_string(), "01");
+    }
+
+    #[test]
+    fn base64_vectors() {
+        let encoded = [
+            "", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy"
+        ];
+        for (&octets, text) in VECTORS.iter().zip(encoded) {
+            let unpadded = text.trim_end_matches('=');
+            assert_eq!(base64(octets).to_string(), text);
+            assert_eq!(base64(octets).without_padding().to_string(), unpadded);
+            assert_eq!(decoded(|i, t| decode_base64(i, t), text), octets);
+            assert_eq!(decoded(|i, t| decode_base64(i, t), unpadded), octets);
+        }
+        assert_eq!(base64(b"\xfb\xff").to_string(), "+/8=");
+        assert_eq!(base64_url(b"\xfb\xff").to_string(), "-_8=");
+        assert_eq!(
+            decoded(|i, t| decode_base64_url(i, t), "-_8"), b"\xfb\xff"
+        );
+    }
+
+    #[test]
+    fn base32hex_vectors() {
+        let encoded = [
+            "", "CO======", "CPNG====", "CPNMU===", "CPNMUOG=", "CPNMUOJ1",
+            "CPNMUOJ1E8======"
+        ];
+        for (&octets, text) in VECTORS.iter().zip(encoded) {
+            let lower = text.trim_end_matches('=').to_ascii_lowercase();
+            assert_eq!(
+                base32hex(octets).upper().with_padding().to_string(), text
+            );
+            assert_eq!(base32hex(octets).to_string(), lower);
+            assert_eq!(decoded(|i, t| decode_base32hex(i, t), text), octets);
+            assert_eq!(
+                decoded(|i, t| decode_base32hex(i, t), &lower), octets
+            );
+        }
+    }
+
+    #[test]
+    fn hexdump_lines() {
+        assert_eq!(hexdump(b"").to_string(), "");
+        assert_eq!(
+            hexdump(b"Hello, world!\n").to_string(),
+            concat!(
+                "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a",
+                "        |Hello, world!.|"
+            )
+        );
+        assert_eq!(
+            hexdump(b"0123456789abcdefXY").with_offset(0x20).with_mark(16)
+                .to_string(),
+            concat!(
+                "00000020  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66",
+                "  |0123456789abcdef|\n",
+                "00000030 [58]59                                            ",
+                " |XY|"
+            )
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        fn check(
+            decode: fn(&str, &mut Vec<u8>) -> Result<(), DecodeError>,
+            input: &str, pos: usize,
+        ) {
+            assert_eq!(
+                decode(input, &mut Vec::new()), Err(DecodeError::Invalid(pos)),
+                "{}", input
+            );
+        }
+
+        check(|i, t| decode_hex(i, t), "0g", 1);
+        check(|i, t| decode_hex(i, t), "012", 3);
+        check(|i, t| decode_base64(i, t), "Zm9v!g==", 4);
+        check(|i, t| decode_base64(i, t), "Zm9vY", 5);
+        check(|i, t| decode_base64(i, t), "Zh==", 1);
+        check(|i, t| decode_base64(i, t), "Zg=", 2);
+        check(|i, t| decode_base64(i, t), "Zg==Zg==", 2);
+        check(|i, t| decode_base64(i, t), "-_8=", 0);
+        check(|i, t| decode_base32hex(i, t), "CPNMUOJ1E", 9);
+        check(|i, t| decode_base32hex(i, t), "CW", 1);
+        check(|i, t| decode_base32hex(i, t), "CP", 1);
+    }
+
+    #[test]
+    fn decode_short_buf() {
+        let mut buf = crate::array::Array::<2>::new();
+        assert_eq!(
+            decode_hex("010203", &mut buf), Err(DecodeError::ShortBuf)
+        );
+        assert_eq!(buf.as_slice(), b"\x01\x02");
+    }
+}
@@ -19,10 +19,21 @@
 //! * `smallvec` for a smallvec for item type `u8` from the
 //!   [smallvec](https://crates.io/crates/smallvec) crate.
 //!
+//! The `zeroize` feature implements the `Zeroize` trait from the
+//! [zeroize](https://crates.io/crates/zeroize) crate for the types of this
+//! crate and makes [`Array`] wipe discarded octets when it is truncated.
+//!
+//! The `arbitrary` and `proptest` features implement the `Arbitrary` traits
+//! of the [arbitrary](https://crates.io/crates/arbitrary) and
+//! [proptest](https://crates.io/crates/proptest) crates, respectively, for
+//! [`Array`], [`Str`], and [`StrBuilder`].
+//!
 //! A number of additional modules exist that provide a few helpful things:
 //!
 //! * The _[mod@array]_ module provides an octets builder backed by an octets
 //!   array.
+//! * The _[encoding]_ module provides hex, base64, and base32hex encoding
+//!   and decoding for octets sequences.
 //! * The _[mod@str]_ module provides both immutable and buildable string types
 //!   that are generic over the octets sequence they wrap.
 //! * The
@@ -31,6 +42,11 @@
 //!   module, which needs to be enabled via the `serde`
 //!   feature, provides traits and functions to more efficiently serialize
 //!   octets sequences.
+//! * The
+#![cfg_attr(feature = "proptest", doc = "  _[proptest]_")]
+#![cfg_attr(not(feature = "proptest"), doc = "  _proptest_")]
+//!   module, which needs to be enabled via the `proptest` feature, provides
+//!   strategies for generating parsers.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(renamed_and_removed_lints)]
@@ -46,12 +62,16 @@ pub use self::builder::{
     ShortBuf, Truncate,
 };
 pub use self::octets::{Octets, OctetsFrom, OctetsInto};
-pub use self::parse::{Parser, ShortInput};
-pub use self::str::{Str, StrBuilder};
+pub use self::parse::{ParseStrError, Parser, ParserMut, ShortInput};
+pub use self::str::{
+    ArrayString, AsciiStr, AsciiStrBuilder, Str, StrBuilder
+};
 
 pub mod array;
 pub mod builder;
+pub mod encoding;
 pub mod octets;
 pub mod parse;
+pub mod proptest;
 pub mod serde;
 pub mod str;
@@ -251,9 +251,54 @@ impl<Source, Target: OctetsFrom<Source>> OctetsInto<Target> for Source {
 }
 
 
+//------------ constant_time_eq ----------------------------------------------
+
+/// Compares two octets sequences in constant time.
+///
+/// Unlike the `PartialEq` impls of octets types, which stop comparing at
+/// the first difference, the function always looks at all octets if both
+/// sequences have the same length. It can thus be used to compare secrets
+/// such as message authentication codes without leaking the position of
+/// the first difference through timing.
+///
+/// The length of the sequences is not considered secret. If they differ
+/// in length, the function returns `false` right away.
+pub fn constant_time_eq<Left, Right>(left: &Left, right: &Right) -> bool
+where
+    Left: AsRef<[u8]> + ?Sized,
+    Right: AsRef<[u8]> + ?Sized,
+{
+    let (left, right) = (left.as_ref(), right.as_ref());
+    if left.len() != right.len() {
+        return false
+    }
+    let diff = left.iter().zip(right).fold(0u8, |diff, (l, r)| {
+        core::hint::black_box(diff | (l ^ r))
+    });
+    diff == 0
+}
+
+
 //------------ SmallOctets ---------------------------------------------------
 
 /// A octets vector that doesn’t allocate for small sizes.
 #[cfg(feature = "smallvec")]
 pub type SmallOctets = smallvec::SmallVec<[u8; 24]>;
 
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn constant_time_eq() {
+        use super::constant_time_eq;
+
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"abc", "abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+        assert!(!constant_time_eq(b"\x80bc", b"abc"));
+    }
+}
@@ -2,11 +2,26 @@
 //!
 //! Parsing is a little more complicated since encoded data may very well be
 //! broken or ambiguously encoded. The helper type [`Parser`] wraps an octets
-//! ref and allows to parse values from the octets.
+//! ref and allows to parse values from the octets. Values can be taken
+//! from the front of the remaining data, advancing the parser’s position,
+//! or from its back via the methods ending in `_back`, which shrink the
+//! parser’s length instead.
+//!
+//! Strings can be taken via methods such as [`Parser::parse_str`]. These
+//! check that the octets are correctly encoded UTF-8 and return a
+//! [`Str`] atop the octets. Errors are reported via [`ParseStrError`].
+//!
+//! If the octets need to be modified while parsing, e.g., to patch
+//! individual fields of a message in place, [`ParserMut`] wraps a mutable
+//! octets ref and additionally allows overwriting octets at the current
+//! position.
 
-use core::fmt;
+use core::{cmp, fmt};
 use core::ops::{Bound, RangeBounds};
+use core::str::Utf8Error;
+use crate::encoding::{hex, hexdump, HexDump};
 use crate::octets::Octets;
+use crate::str::Str;
 
 //------------ Parser --------------------------------------------------------
 
@@ -15,10 +30,838 @@ use crate::octets::Octets;
 /// The parser wraps an [Octets] reference and remembers the read position on
 /// the referenced sequence. Methods allow reading out data and progressing
 /// the position beyond processed data.
-#[derive(Debug)]
+///
+/// The `Debug` implementation shows the position and length of the parser
+/// as well as the next few octets. The alternate form, i.e., `{:#?}`, shows
+/// a hex dump of the lines around the current position instead with the
+/// octet at the position enclosed in square brackets.
 pub struct Parser<'a, Octs: ?Sized> {
     /// The underlying octets reference.
-    octets: &'a Octs,
+    octets: &'a Octs,
+
+    /// The current position of the parser from the beginning of `octets`.
+    pos: usize,
+
+    /// The length of the octets sequence.
+    len: usize,
+}
+
+impl<'a, Octs: ?Sized> Parser<'a, Octs> {
+    /// Creates a new parser atop a reference to an octet sequence.
+    pub fn from_ref(octets: &'a Octs) -> Self
+    where
+        Octs: AsRef<[u8]>,
+    {
+        Parser {
+            pos: 0,
+            len: octets.as_ref().len(),
+            octets,
+        }
+    }
+
+    /// Creates a new parser only using a range of the given octets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is decreasing or out of bounds.
+    pub fn with_range<R>(octets: &'a Octs, range: R) -> Self
+    where
+        Octs: AsRef<[u8]>,
+        R: RangeBounds<usize>
+    {
+        match Self::_try_with_range(octets, range) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    /// Creates a new parser only using a range if possible.
+    ///
+    /// If `range` is decreasing or out of bounds, returns an Error.
+    pub fn try_with_range<R>(
+        octets: &'a Octs, range: R
+    ) -> Option<Self>
+    where
+        Octs: AsRef<[u8]>,
+        R: RangeBounds<usize>
+    {
+        Self::_try_with_range(octets, range).ok()
+    }
+
+    /// Creates a new parser only using a range if possible.
+    ///
+    /// If `range` is decreasing or out of bounds, returns an Error.
+    fn _try_with_range<R>(
+        octets: &'a Octs, range: R
+    ) -> Result<Self, &'static str>
+    where
+        Octs: AsRef<[u8]>,
+        R: RangeBounds<usize>
+    {
+        let (pos, len) = check_range(octets.as_ref().len(), range)?;
+        Ok(
+            Parser {
+                pos,
+                len,
+                octets
+            }
+        )
+    }
+
+    /// Returns the wrapped reference to the underlying octets sequence.
+    pub fn octets_ref(&self) -> &'a Octs {
+        self.octets
+    }
+
+    /// Returns the current parse position as an index into the octets.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the length of the underlying octet sequence.
+    ///
+    /// This is _not_ the number of octets left for parsing. Use
+    /// [`Parser::remaining`] for that.
+    ///
+    /// If octets have been taken from the end of the parser via one of
+    /// the `_back` methods, the length is reduced accordingly.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the underlying octets sequence is empty.
+    ///
+    /// This does _not_ return whether there are no more octets left to parse.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Parser<'static, [u8]> {
+    /// Creates a new parser atop a static byte slice.
+    ///
+    /// This function is most useful for testing.
+    pub fn from_static(slice: &'static [u8]) -> Self {
+        Self::from_ref(slice)
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
+    /// Returns an octets slice of the underlying sequence.
+    ///
+    /// The slice covers the entire sequence, not just the remaining data. You
+    /// can use [`Parser::peek`] for that.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.octets.as_ref()[..self.len]
+    }
+
+    /// Returns the number of remaining octets to parse.
+    pub fn remaining(&self) -> usize {
+        self.len - self.pos
+    }
+
+    /// Returns a slice for the next `len` octets.
+    ///
+    /// If less than `len` octets are left, returns an error.
+    pub fn peek(&self, len: usize) -> Result<&[u8], ShortInput> {
+        self.check_len(len)?;
+        Ok(&self.peek_all()[..len])
+    }
+
+    /// Returns a slice of the data left to parse.
+    pub fn peek_all(&self) -> &[u8] {
+        &self.octets.as_ref()[self.pos..self.len]
+    }
+
+    /// Repositions the parser to the given index.
+    ///
+    /// It is okay to reposition anywhere within the sequence. However,
+    /// if `pos` is larger than the length of the sequence, an error is
+    /// returned.
+    pub fn seek(&mut self, pos: usize) -> Result<(), ShortInput> {
+        if pos > self.len {
+            Err(ShortInput(()))
+        } else {
+            self.pos = pos;
+            Ok(())
+        }
+    }
+
+    /// Advances the parser‘s position by `len` octets.
+    ///
+    /// If this would take the parser beyond its end, an error is returned.
+    pub fn advance(&mut self, len: usize) -> Result<(), ShortInput> {
+        if len > self.remaining() {
+            Err(ShortInput(()))
+        } else {
+            self.pos += len;
+            Ok(())
+        }
+    }
+
+    /// Advances to the end of the parser.
+    pub fn advance_to_end(&mut self) {
+        self.pos = self.len
+    }
+
+    /// Checks that there are `len` octets left to parse.
+    ///
+    /// If there aren’t, returns an error.
+    pub fn check_len(&self, len: usize) -> Result<(), ShortInput> {
+        if self.remaining() < len {
+            Err(ShortInput(()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
+    /// Takes and returns the next `len` octets.
+    ///
+    /// Advances the parser by `len` octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_octets(
+        &mut self,
+        len: usize,
+    ) -> Result<Octs::Range<'a>, ShortInput>
+    where
+        Octs: Octets,
+    {
+        self.check_len(len)?;
+        let end = self.pos + len;
+        let res = self.octets.range(self.pos..end);
+        self.pos = end;
+        Ok(res)
+    }
+
+    /// Fills the provided buffer by taking octets from the parser.
+    ///
+    /// Copies as many octets as the buffer is long from the parser into the
+    /// buffer and advances the parser by that many octets.
+    ///
+    /// If there aren’t enough octets left in the parser to fill the buffer
+    /// completely, returns an error and leaves the parser untouched.
+    pub fn parse_buf(&mut self, buf: &mut [u8]) -> Result<(), ShortInput> {
+        let pos = self.pos;
+        self.advance(buf.len())?;
+        buf.copy_from_slice(&self.octets.as_ref()[pos..self.pos]);
+        Ok(())
+    }
+
+    /// Takes as many octets as requested and returns a parser for them.
+    ///
+    /// If enough octets are remaining, the method clones `self`, limits
+    /// its length to the requested number of octets, and returns it. The
+    /// returned parser will be positioned at wherever `self` was positioned.
+    /// The `self` parser will be advanced by the requested amount of octets.
+    ///
+    /// If there aren’t enough octets left in the parser to fill the buffer
+    /// completely, returns an error and leaves the parser untouched.
+    pub fn parse_parser(&mut self, len: usize) -> Result<Self, ShortInput> {
+        self.check_len(len)?;
+        let mut res = *self;
+        res.len = res.pos + len;
+        self.pos += len;
+        Ok(res)
+    }
+
+    /// Takes an `i8` from the beginning of the parser.
+    ///
+    /// Advances the parser by one octet. If there aren’t enough octets left,
+    /// leaves the parser untouched and returns an error instead.
+    pub fn parse_i8(&mut self) -> Result<i8, ShortInput> {
+        let res = self.peek(1)?[0] as i8;
+        self.pos += 1;
+        Ok(res)
+    }
+
+    /// Takes a `u8` from the beginning of the parser.
+    ///
+    /// Advances the parser by one octet. If there aren’t enough octets left,
+    /// leaves the parser untouched and returns an error instead.
+    pub fn parse_u8(&mut self) -> Result<u8, ShortInput> {
+        let res = self.peek(1)?[0];
+        self.pos += 1;
+        Ok(res)
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
+    /// Takes a big-endian `i16` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by two octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i16_be(&mut self) -> Result<i16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf(&mut res)?;
+        Ok(i16::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `i16` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by two octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i16_le(&mut self) -> Result<i16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf(&mut res)?;
+        Ok(i16::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u16` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by two octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u16_be(&mut self) -> Result<u16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf(&mut res)?;
+        Ok(u16::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u16` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by two octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u16_le(&mut self) -> Result<u16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf(&mut res)?;
+        Ok(u16::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `i32` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by four octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i32_be(&mut self) -> Result<i32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf(&mut res)?;
+        Ok(i32::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `i32` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by four octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i32_le(&mut self) -> Result<i32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf(&mut res)?;
+        Ok(i32::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u32` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by four octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u32_be(&mut self) -> Result<u32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf(&mut res)?;
+        Ok(u32::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u32` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by four octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u32_le(&mut self) -> Result<u32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf(&mut res)?;
+        Ok(u32::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `i64` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by eight octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i64_be(&mut self) -> Result<i64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf(&mut res)?;
+        Ok(i64::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `i64` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by eight octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i64_le(&mut self) -> Result<i64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf(&mut res)?;
+        Ok(i64::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u64` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by eight octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u64_be(&mut self) -> Result<u64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf(&mut res)?;
+        Ok(u64::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u64` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by eight octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u64_le(&mut self) -> Result<u64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf(&mut res)?;
+        Ok(u64::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `i128` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by 16 octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i128_be(&mut self) -> Result<i128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf(&mut res)?;
+        Ok(i128::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `i128` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by 16 octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_i128_le(&mut self) -> Result<i128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf(&mut res)?;
+        Ok(i128::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u128` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by 16 octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u128_be(&mut self) -> Result<u128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf(&mut res)?;
+        Ok(u128::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u128` from the beginning of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The parser is advanced by 16 octets. If there aren’t enough octets
+    /// left, leaves the parser untouched and returns an error instead.
+    pub fn parse_u128_le(&mut self) -> Result<u128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf(&mut res)?;
+        Ok(u128::from_le_bytes(res))
+    }
+}
+
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
+    /// Returns a slice for the last `len` octets left to parse.
+    ///
+    /// If less than `len` octets are left, returns an error.
+    pub fn peek_back(&self, len: usize) -> Result<&[u8], ShortInput> {
+        self.check_len(len)?;
+        Ok(&self.octets.as_ref()[self.len - len..self.len])
+    }
+
+    /// Moves the end of the parser back by `len` octets.
+    ///
+    /// This excludes the last `len` octets from parsing. If this would
+    /// move the end before the current position, an error is returned
+    /// and the parser is left untouched.
+    pub fn retreat_end(&mut self, len: usize) -> Result<(), ShortInput> {
+        self.check_len(len)?;
+        self.len -= len;
+        Ok(())
+    }
+
+    /// Takes and returns the last `len` octets left to parse.
+    ///
+    /// Rather than advancing the position, the method moves the end of the
+    /// parser back by `len` octets, i.e., [`len`][Self::len] will shrink.
+    /// If there aren’t enough octets left, leaves the parser untouched and
+    /// returns an error instead.
+    pub fn parse_octets_back(
+        &mut self,
+        len: usize,
+    ) -> Result<Octs::Range<'a>, ShortInput>
+    where
+        Octs: Octets,
+    {
+        self.check_len(len)?;
+        let res = self.octets.range(self.len - len..self.len);
+        self.len -= len;
+        Ok(res)
+    }
+
+    /// Fills the provided buffer by taking octets from the end of the parser.
+    ///
+    /// Copies as many octets as the buffer is long from the end of the
+    /// remaining data into the buffer and moves the end of the parser back
+    /// by that many octets.
+    ///
+    /// If there aren’t enough octets left in the parser to fill the buffer
+    /// completely, returns an error and leaves the parser untouched.
+    pub fn parse_buf_back(
+        &mut self, buf: &mut [u8]
+    ) -> Result<(), ShortInput> {
+        buf.copy_from_slice(self.peek_back(buf.len())?);
+        self.len -= buf.len();
+        Ok(())
+    }
+
+    /// Takes as many octets as requested from the end and returns a parser.
+    ///
+    /// If enough octets are remaining, the method clones `self` and
+    /// positions it at the start of the last `len` remaining octets. The
+    /// end of `self` will be moved back by the requested amount of octets.
+    ///
+    /// If there aren’t enough octets left, returns an error and leaves the
+    /// parser untouched.
+    pub fn parse_parser_back(
+        &mut self, len: usize
+    ) -> Result<Self, ShortInput> {
+        self.check_len(len)?;
+        let mut res = *self;
+        self.len -= len;
+        res.pos = self.len;
+        Ok(res)
+    }
+
+    /// Takes an `i8` from the end of the parser.
+    ///
+    /// Moves the end of the parser back by one octet. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i8_back(&mut self) -> Result<i8, ShortInput> {
+        let res = self.peek_back(1)?[0] as i8;
+        self.len -= 1;
+        Ok(res)
+    }
+
+    /// Takes a `u8` from the end of the parser.
+    ///
+    /// Moves the end of the parser back by one octet. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u8_back(&mut self) -> Result<u8, ShortInput> {
+        let res = self.peek_back(1)?[0];
+        self.len -= 1;
+        Ok(res)
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
+    /// Takes an big-endian `i16` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by two octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i16_be_back(&mut self) -> Result<i16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf_back(&mut res)?;
+        Ok(i16::from_be_bytes(res))
+    }
+
+    /// Takes an little-endian `i16` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by two octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i16_le_back(&mut self) -> Result<i16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf_back(&mut res)?;
+        Ok(i16::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u16` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by two octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u16_be_back(&mut self) -> Result<u16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf_back(&mut res)?;
+        Ok(u16::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u16` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by two octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u16_le_back(&mut self) -> Result<u16, ShortInput> {
+        let mut res = [0; 2];
+        self.parse_buf_back(&mut res)?;
+        Ok(u16::from_le_bytes(res))
+    }
+
+    /// Takes an big-endian `i32` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by four octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i32_be_back(&mut self) -> Result<i32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf_back(&mut res)?;
+        Ok(i32::from_be_bytes(res))
+    }
+
+    /// Takes an little-endian `i32` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by four octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i32_le_back(&mut self) -> Result<i32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf_back(&mut res)?;
+        Ok(i32::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u32` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by four octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u32_be_back(&mut self) -> Result<u32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf_back(&mut res)?;
+        Ok(u32::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u32` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by four octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u32_le_back(&mut self) -> Result<u32, ShortInput> {
+        let mut res = [0; 4];
+        self.parse_buf_back(&mut res)?;
+        Ok(u32::from_le_bytes(res))
+    }
+
+    /// Takes an big-endian `i64` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by eight octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i64_be_back(&mut self) -> Result<i64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf_back(&mut res)?;
+        Ok(i64::from_be_bytes(res))
+    }
+
+    /// Takes an little-endian `i64` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by eight octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i64_le_back(&mut self) -> Result<i64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf_back(&mut res)?;
+        Ok(i64::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u64` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by eight octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u64_be_back(&mut self) -> Result<u64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf_back(&mut res)?;
+        Ok(u64::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u64` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by eight octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u64_le_back(&mut self) -> Result<u64, ShortInput> {
+        let mut res = [0; 8];
+        self.parse_buf_back(&mut res)?;
+        Ok(u64::from_le_bytes(res))
+    }
+
+    /// Takes an big-endian `i128` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i128_be_back(&mut self) -> Result<i128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf_back(&mut res)?;
+        Ok(i128::from_be_bytes(res))
+    }
+
+    /// Takes an little-endian `i128` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_i128_le_back(&mut self) -> Result<i128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf_back(&mut res)?;
+        Ok(i128::from_le_bytes(res))
+    }
+
+    /// Takes a big-endian `u128` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u128_be_back(&mut self) -> Result<u128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf_back(&mut res)?;
+        Ok(u128::from_be_bytes(res))
+    }
+
+    /// Takes a little-endian `u128` from the end of the parser.
+    ///
+    /// The value is converted into the system’s own byte order if necessary.
+    /// The end of the parser is moved back by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_u128_le_back(&mut self) -> Result<u128, ShortInput> {
+        let mut res = [0; 16];
+        self.parse_buf_back(&mut res)?;
+        Ok(u128::from_le_bytes(res))
+    }
+}
+
+impl<'a, Octs: Octets + ?Sized> Parser<'a, Octs> {
+    /// Takes a string of `len` octets from the beginning of the parser.
+    ///
+    /// The octets must be correctly encoded UTF-8. Advances the parser by
+    /// `len` octets. If there aren’t enough octets left or they aren’t
+    /// valid UTF-8, leaves the parser untouched and returns an error.
+    pub fn parse_str(
+        &mut self, len: usize
+    ) -> Result<Str<Octs::Range<'a>>, ParseStrError> {
+        let start = self.pos;
+        if let Err(error) = core::str::from_utf8(self.peek(len)?) {
+            return Err(ParseStrError::InvalidUtf8 {
+                offset: start + error.valid_up_to(),
+                error
+            })
+        }
+        let octets = self.parse_octets(len)?;
+        Ok(unsafe { Str::from_utf8_unchecked(octets) })
+    }
+
+    /// Takes a string preceded by a one octet length.
+    ///
+    /// The length is the number of octets of the string itself. If there
+    /// aren’t enough octets left or they aren’t valid UTF-8, leaves the
+    /// parser untouched and returns an error.
+    pub fn parse_str_u8_prefixed(
+        &mut self
+    ) -> Result<Str<Octs::Range<'a>>, ParseStrError> {
+        let start = self.pos;
+        let len = self.parse_u8()?;
+        self.parse_str(len.into()).inspect_err(|_| self.pos = start)
+    }
+
+    /// Takes a string preceded by a big-endian two octet length.
+    ///
+    /// The length is the number of octets of the string itself. If there
+    /// aren’t enough octets left or they aren’t valid UTF-8, leaves the
+    /// parser untouched and returns an error.
+    pub fn parse_str_u16_be_prefixed(
+        &mut self
+    ) -> Result<Str<Octs::Range<'a>>, ParseStrError> {
+        let start = self.pos;
+        let len = self.parse_u16_be()?;
+        self.parse_str(len.into()).inspect_err(|_| self.pos = start)
+    }
+
+    /// Takes a string preceded by a little-endian two octet length.
+    ///
+    /// The length is the number of octets of the string itself. If there
+    /// aren’t enough octets left or they aren’t valid UTF-8, leaves the
+    /// parser untouched and returns an error.
+    pub fn parse_str_u16_le_prefixed(
+        &mut self
+    ) -> Result<Str<Octs::Range<'a>>, ParseStrError> {
+        let start = self.pos;
+        let len = self.parse_u16_le()?;
+        self.parse_str(len.into()).inspect_err(|_| self.pos = start)
+    }
+
+    /// Takes a string terminated by a zero octet.
+    ///
+    /// The returned string does not include the terminating zero octet
+    /// but the parser is advanced past it. If there is no zero octet in
+    /// the remaining data, returns a short input error. If there are
+    /// invalid UTF-8 sequences, returns an error. In both cases, leaves
+    /// the parser untouched.
+    pub fn parse_str_nul_terminated(
+        &mut self
+    ) -> Result<Str<Octs::Range<'a>>, ParseStrError> {
+        let len = self.peek_all().iter().position(|&ch| ch == 0).ok_or(
+            ShortInput(())
+        )?;
+        let res = self.parse_str(len)?;
+        self.pos += 1;
+        Ok(res)
+    }
+}
+
+
+//--- Clone and Copy
+
+impl<'a, Octs: ?Sized> Clone for Parser<'a, Octs> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Octs: ?Sized> Copy for Parser<'a, Octs> { }
+
+
+//------------ ParserMut -----------------------------------------------------
+
+/// A parser that can also modify the octets sequence it is parsing.
+///
+/// The parser wraps a mutable reference to an octets sequence and
+/// remembers the current position on the referenced sequence. In addition
+/// to the reading methods of [`Parser`], it provides methods to overwrite
+/// the octets at the current position, e.g., to patch individual fields of
+/// a message in place.
+///
+/// Both reading and writing advance the position of the parser. Neither
+/// will ever go beyond the end of the parser. Instead, if there aren’t
+/// enough octets left, an error is returned.
+pub struct ParserMut<'a, Octs: ?Sized> {
+    /// The underlying octets reference.
+    octets: &'a mut Octs,
 
     /// The current position of the parser from the beginning of `octets`.
     pos: usize,
@@ -27,13 +870,13 @@ pub struct Parser<'a, Octs: ?Sized> {
     len: usize,
 }
 
-impl<'a, Octs: ?Sized> Parser<'a, Octs> {
-    /// Creates a new parser atop a reference to an octet sequence.
-    pub fn from_ref(octets: &'a Octs) -> Self
+impl<'a, Octs: ?Sized> ParserMut<'a, Octs> {
+    /// Creates a new parser atop a mutable reference to an octet sequence.
+    pub fn from_mut(octets: &'a mut Octs) -> Self
     where
         Octs: AsRef<[u8]>,
     {
-        Parser {
+        ParserMut {
             pos: 0,
             len: octets.as_ref().len(),
             octets,
@@ -45,80 +888,43 @@ impl<'a, Octs: ?Sized> Parser<'a, Octs> {
     /// # Panics
     ///
     /// Panics if `range` is decreasing or out of bounds.
-    pub fn with_range<R>(octets: &'a Octs, range: R) -> Self
+    pub fn with_range<R>(octets: &'a mut Octs, range: R) -> Self
     where
         Octs: AsRef<[u8]>,
         R: RangeBounds<usize>
     {
-        match Self::_try_with_range(octets, range) {
-            Ok(p) => p,
+        match check_range(octets.as_ref().len(), range) {
+            Ok((pos, len)) => ParserMut { pos, len, octets },
             Err(e) => panic!("{}", e)
         }
     }
 
     /// Creates a new parser only using a range if possible.
     ///
-    /// If `range` is decreasing or out of bounds, returns an Error.
+    /// If `range` is decreasing or out of bounds, returns `None`.
     pub fn try_with_range<R>(
-        octets: &'a Octs, range: R
+        octets: &'a mut Octs, range: R
     ) -> Option<Self>
     where
         Octs: AsRef<[u8]>,
         R: RangeBounds<usize>
     {
-        Self::_try_with_range(octets, range).ok()
+        let (pos, len) = check_range(octets.as_ref().len(), range).ok()?;
+        Some(ParserMut { pos, len, octets })
     }
 
-    /// Creates a new parser only using a range if possible.
-    ///
-    /// If `range` is decreasing or out of bounds, returns an Error.
-    fn _try_with_range<R>(
-        octets: &'a Octs, range: R
-    ) -> Result<Self, &'static str>
-    where
-        Octs: AsRef<[u8]>,
-        R: RangeBounds<usize>
-    {
-        let octets_len = octets.as_ref().len();
-
-        let pos = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(n) => *n,
-            Bound::Excluded(n) => *n + 1,
-        };
-
-        if pos > octets_len {
-            return Err("range start is out of range for octets")
-        }
-
-        let len = match range.end_bound() {
-            Bound::Unbounded => octets_len,
-            Bound::Excluded(n) => *n,
-            Bound::Included(n) => *n + 1,
-        };
-
-        if len > octets_len {
-            return Err("range end is out of range for octets")
-        }
-
-        if len < pos {
-            return Err("range starts after end")
-        }
-
-        Ok(
-            Parser {
-                pos,
-                len,
-                octets
-            }
-        )
-
+    /// Returns a reference to the underlying octets sequence.
+    pub fn octets_ref(&self) -> &Octs {
+        self.octets
     }
 
+    /// Returns a mutable reference to the underlying octets sequence.
+    pub fn octets_mut(&mut self) -> &mut Octs {
+        self.octets
+    }
 
-
-    /// Returns the wrapped reference to the underlying octets sequence.
-    pub fn octets_ref(&self) -> &'a Octs {
+    /// Converts the parser into the wrapped mutable octets reference.
+    pub fn into_octets_mut(self) -> &'a mut Octs {
         self.octets
     }
 
@@ -130,7 +936,7 @@ impl<'a, Octs: ?Sized> Parser<'a, Octs> {
     /// Returns the length of the underlying octet sequence.
     ///
     /// This is _not_ the number of octets left for parsing. Use
-    /// [`Parser::remaining`] for that.
+    /// [`ParserMut::remaining`] for that.
     pub fn len(&self) -> usize {
         self.len
     }
@@ -141,22 +947,28 @@ impl<'a, Octs: ?Sized> Parser<'a, Octs> {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
-}
 
-impl Parser<'static, [u8]> {
-    /// Creates a new parser atop a static byte slice.
+    /// Returns a read-only parser for the same octets and position.
     ///
-    /// This function is most useful for testing.
-    pub fn from_static(slice: &'static [u8]) -> Self {
-        Self::from_ref(slice)
+    /// The returned parser borrows from `self`, so `self` cannot be used
+    /// while the returned parser is alive.
+    pub fn as_parser(&self) -> Parser<'_, Octs> {
+        Parser { octets: self.octets, pos: self.pos, len: self.len }
+    }
+
+    /// Converts the parser into a read-only parser.
+    ///
+    /// The returned parser will keep the position and length of `self`.
+    pub fn into_parser(self) -> Parser<'a, Octs> {
+        Parser { octets: self.octets, pos: self.pos, len: self.len }
     }
 }
 
-impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParserMut<'a, Octs> {
     /// Returns an octets slice of the underlying sequence.
     ///
     /// The slice covers the entire sequence, not just the remaining data. You
-    /// can use [`Parser::peek`] for that.
+    /// can use [`ParserMut::peek`] for that.
     pub fn as_slice(&self) -> &[u8] {
         &self.octets.as_ref()[..self.len]
     }
@@ -222,25 +1034,33 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
     }
 }
 
-impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
-    /// Takes and returns the next `len` octets.
+impl<'a, Octs: AsRef<[u8]> + AsMut<[u8]> + ?Sized> ParserMut<'a, Octs> {
+    /// Returns a mutable slice for the next `len` octets.
+    ///
+    /// If less than `len` octets are left, returns an error.
+    pub fn peek_mut(&mut self, len: usize) -> Result<&mut [u8], ShortInput> {
+        self.check_len(len)?;
+        Ok(&mut self.peek_all_mut()[..len])
+    }
+
+    /// Returns a mutable slice of the data left to parse.
+    pub fn peek_all_mut(&mut self) -> &mut [u8] {
+        &mut self.octets.as_mut()[self.pos..self.len]
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParserMut<'a, Octs> {
+    /// Takes and returns the next `len` octets as a slice.
     ///
     /// Advances the parser by `len` octets. If there aren’t enough octets
     /// left, leaves the parser untouched and returns an error instead.
-    pub fn parse_octets(
-        &mut self,
-        len: usize,
-    ) -> Result<Octs::Range<'a>, ShortInput>
-    where
-        Octs: Octets,
-    {
-        let end = self.pos + len;
-        if end > self.len {
-            return Err(ShortInput(()));
-        }
-        let res = self.octets.range(self.pos..end);
-        self.pos = end;
-        Ok(res)
+    ///
+    /// Because the parser holds a mutable reference, the returned slice
+    /// borrows from the parser rather than the underlying octets sequence.
+    pub fn parse_slice(&mut self, len: usize) -> Result<&[u8], ShortInput> {
+        let pos = self.pos;
+        self.advance(len)?;
+        Ok(&self.octets.as_ref()[pos..self.pos])
     }
 
     /// Fills the provided buffer by taking octets from the parser.
@@ -259,19 +1079,21 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
 
     /// Takes as many octets as requested and returns a parser for them.
     ///
-    /// If enough octets are remaining, the method clones `self`, limits
-    /// its length to the requested number of octets, and returns it. The
-    /// returned parser will be positioned at wherever `self` was positioned.
-    /// The `self` parser will be advanced by the requested amount of octets.
+    /// If enough octets are remaining, the method returns a parser
+    /// reborrowing the octets of `self` limited to the requested number of
+    /// octets. The returned parser will be positioned at wherever `self`
+    /// was positioned. The `self` parser will be advanced by the requested
+    /// amount of octets.
     ///
-    /// If there aren’t enough octets left in the parser to fill the buffer
-    /// completely, returns an error and leaves the parser untouched.
-    pub fn parse_parser(&mut self, len: usize) -> Result<Self, ShortInput> {
+    /// If there aren’t enough octets left in the parser, returns an error
+    /// and leaves the parser untouched.
+    pub fn parse_parser(
+        &mut self, len: usize
+    ) -> Result<ParserMut<'_, Octs>, ShortInput> {
         self.check_len(len)?;
-        let mut res = *self;
-        res.len = res.pos + len;
+        let pos = self.pos;
         self.pos += len;
-        Ok(res)
+        Ok(ParserMut { octets: self.octets, pos, len: pos + len })
     }
 
     /// Takes an `i8` from the beginning of the parser.
@@ -295,8 +1117,8 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
     }
 }
 
-impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
-    /// Takes a big-endian `i16` from the beginning of the parser.
+impl<'a, Octs: AsRef<[u8]> + ?Sized> ParserMut<'a, Octs> {
+    /// Takes an big-endian `i16` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by two octets. If there aren’t enough octets
@@ -307,7 +1129,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(i16::from_be_bytes(res))
     }
 
-    /// Takes a little-endian `i16` from the beginning of the parser.
+    /// Takes an little-endian `i16` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by two octets. If there aren’t enough octets
@@ -340,7 +1162,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(u16::from_le_bytes(res))
     }
 
-    /// Takes a big-endian `i32` from the beginning of the parser.
+    /// Takes an big-endian `i32` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by four octets. If there aren’t enough octets
@@ -351,7 +1173,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(i32::from_be_bytes(res))
     }
 
-    /// Takes a little-endian `i32` from the beginning of the parser.
+    /// Takes an little-endian `i32` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by four octets. If there aren’t enough octets
@@ -384,7 +1206,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(u32::from_le_bytes(res))
     }
 
-    /// Takes a big-endian `i64` from the beginning of the parser.
+    /// Takes an big-endian `i64` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by eight octets. If there aren’t enough octets
@@ -395,7 +1217,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(i64::from_be_bytes(res))
     }
 
-    /// Takes a little-endian `i64` from the beginning of the parser.
+    /// Takes an little-endian `i64` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by eight octets. If there aren’t enough octets
@@ -428,7 +1250,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(u64::from_le_bytes(res))
     }
 
-    /// Takes a big-endian `i128` from the beginning of the parser.
+    /// Takes an big-endian `i128` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by 16 octets. If there aren’t enough octets
@@ -439,7 +1261,7 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
         Ok(i128::from_be_bytes(res))
     }
 
-    /// Takes a little-endian `i128` from the beginning of the parser.
+    /// Takes an little-endian `i128` from the beginning of the parser.
     ///
     /// The value is converted into the system’s own byte order if necessary.
     /// The parser is advanced by 16 octets. If there aren’t enough octets
@@ -473,16 +1295,285 @@ impl<'a, Octs: AsRef<[u8]> + ?Sized> Parser<'a, Octs> {
     }
 }
 
+impl<'a, Octs: AsRef<[u8]> + AsMut<[u8]> + ?Sized> ParserMut<'a, Octs> {
+    /// Overwrites the next octets with the content of a slice.
+    ///
+    /// Copies the slice into the octets at the current position and
+    /// advances the parser by the length of the slice. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_slice(&mut self, slice: &[u8]) -> Result<(), ShortInput> {
+        self.peek_mut(slice.len())?.copy_from_slice(slice);
+        self.pos += slice.len();
+        Ok(())
+    }
 
-//--- Clone and Copy
+    /// Overwrites the next octet with an `i8`.
+    ///
+    /// Advances the parser by one octet. If there aren’t enough octets left,
+    /// leaves the parser and the octets untouched and returns an error
+    /// instead.
+    pub fn write_i8(&mut self, value: i8) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
 
-impl<'a, Octs: ?Sized> Clone for Parser<'a, Octs> {
-    fn clone(&self) -> Self {
-        *self
+    /// Overwrites the next octet with a `u8`.
+    ///
+    /// Advances the parser by one octet. If there aren’t enough octets left,
+    /// leaves the parser and the octets untouched and returns an error
+    /// instead.
+    pub fn write_u8(&mut self, value: u8) -> Result<(), ShortInput> {
+        self.write_slice(&[value])
     }
 }
 
-impl<'a, Octs: ?Sized> Copy for Parser<'a, Octs> { }
+impl<'a, Octs: AsRef<[u8]> + AsMut<[u8]> + ?Sized> ParserMut<'a, Octs> {
+    /// Overwrites the next octets with an big-endian `i16`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by two octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i16_be(&mut self, value: i16) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with an little-endian `i16`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by two octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i16_le(&mut self, value: i16) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with a big-endian `u16`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by two octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u16_be(&mut self, value: u16) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with a little-endian `u16`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by two octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u16_le(&mut self, value: u16) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with an big-endian `i32`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by four octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i32_be(&mut self, value: i32) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with an little-endian `i32`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by four octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i32_le(&mut self, value: i32) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with a big-endian `u32`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by four octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u32_be(&mut self, value: u32) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with a little-endian `u32`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by four octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u32_le(&mut self, value: u32) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with an big-endian `i64`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by eight octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i64_be(&mut self, value: i64) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with an little-endian `i64`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by eight octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i64_le(&mut self, value: i64) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with a big-endian `u64`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by eight octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u64_be(&mut self, value: u64) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with a little-endian `u64`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by eight octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u64_le(&mut self, value: u64) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with an big-endian `i128`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i128_be(&mut self, value: i128) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with an little-endian `i128`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_i128_le(&mut self, value: i128) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+
+    /// Overwrites the next octets with a big-endian `u128`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u128_be(&mut self, value: u128) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_be_bytes())
+    }
+
+    /// Overwrites the next octets with a little-endian `u128`.
+    ///
+    /// The value is converted from the system’s own byte order if
+    /// necessary. The parser is advanced by 16 octets. If there aren’t
+    /// enough octets left, leaves the parser and the octets untouched and
+    /// returns an error instead.
+    pub fn write_u128_le(&mut self, value: u128) -> Result<(), ShortInput> {
+        self.write_slice(&value.to_le_bytes())
+    }
+}
+
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Checks that a range is valid for an octets sequence of the given length.
+///
+/// Returns the start and end of the range or an error message if the range
+/// is decreasing or out of bounds.
+fn check_range(
+    octets_len: usize, range: impl RangeBounds<usize>
+) -> Result<(usize, usize), &'static str> {
+    let pos = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(n) => *n,
+        Bound::Excluded(n) => *n + 1,
+    };
+
+    if pos > octets_len {
+        return Err("range start is out of range for octets")
+    }
+
+    let len = match range.end_bound() {
+        Bound::Unbounded => octets_len,
+        Bound::Excluded(n) => *n,
+        Bound::Included(n) => *n + 1,
+    };
+
+    if len > octets_len {
+        return Err("range end is out of range for octets")
+    }
+
+    if len < pos {
+        return Err("range starts after end")
+    }
+
+    Ok((pos, len))
+}
+
+/// Formats a parser for debugging.
+///
+/// In the normal form, shows the next octets in hex. In the alternate form,
+/// shows a hex dump of two lines before and after the current position.
+fn fmt_parser(
+    name: &str, octets: &[u8], pos: usize, len: usize,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    const LINE_LEN: usize = HexDump::LINE_LEN;
+
+    let octets = &octets[..len];
+    if f.alternate() {
+        let line = pos / LINE_LEN;
+        let start = line.saturating_sub(2) * LINE_LEN;
+        let end = cmp::min(len, (line + 3) * LINE_LEN);
+        write!(
+            f, "{} {{\n    pos: {},\n    len: {},\n    window:",
+            name, pos, len
+        )?;
+        hexdump(&octets[start..end]).with_offset(start).with_mark(
+            pos - start
+        ).fmt_indented(f, "        ", true)?;
+        f.write_str("\n}")
+    }
+    else {
+        let end = cmp::min(len, pos + LINE_LEN);
+        f.debug_struct(name)
+            .field("pos", &pos)
+            .field("len", &len)
+            .field("next", &hex(&octets[pos..end]).with_separator(" "))
+            .finish()
+    }
+}
+
+
+//--- Debug
+
+impl<Octs: AsRef<[u8]> + ?Sized> fmt::Debug for Parser<'_, Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_parser("Parser", self.octets.as_ref(), self.pos, self.len, f)
+    }
+}
+
+impl<Octs: AsRef<[u8]> + ?Sized> fmt::Debug for ParserMut<'_, Octs> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_parser("ParserMut", self.octets.as_ref(), self.pos, self.len, f)
+    }
+}
 
 
 //--------- ShortInput -------------------------------------------------------
@@ -502,6 +1593,68 @@ impl fmt::Display for ShortInput {
 impl core::error::Error for ShortInput {}
 
 
+//------------ ParseStrError -------------------------------------------------
+
+/// An error happened while parsing a string.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseStrError {
+    /// The input ended before the end of the string.
+    ShortInput(ShortInput),
+
+    /// The string wasn’t correctly encoded UTF-8.
+    InvalidUtf8 {
+        /// The position of the first invalid octet in the parser’s octets.
+        ///
+        /// Like the parser’s position, this is relative to the start of
+        /// the underlying octets sequence.
+        offset: usize,
+
+        /// The underlying UTF-8 error.
+        ///
+        /// Positions reported by this error are relative to the start of
+        /// the string.
+        error: Utf8Error,
+    },
+}
+
+impl ParseStrError {
+    /// Returns whether the error was caused by short input.
+    pub fn is_short_input(&self) -> bool {
+        matches!(self, ParseStrError::ShortInput(_))
+    }
+}
+
+//--- From
+
+impl From<ShortInput> for ParseStrError {
+    fn from(err: ShortInput) -> Self {
+        ParseStrError::ShortInput(err)
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for ParseStrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseStrError::ShortInput(err) => fmt::Display::fmt(&err, f),
+            ParseStrError::InvalidUtf8 { offset, .. } => {
+                write!(f, "invalid UTF-8 at position {offset}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseStrError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ParseStrError::ShortInput(err) => Some(err),
+            ParseStrError::InvalidUtf8 { error, .. } => Some(error),
+        }
+    }
+}
+
+
 //============ Testing =======================================================
 
 #[cfg(test)]
@@ -580,6 +1733,8 @@ mod test {
         assert_eq!(parser.parse_octets(2).unwrap(), b"01");
         assert_eq!(parser.parse_octets(2).unwrap(), b"23");
         assert!(parser.parse_octets(7).is_err());
+        assert!(parser.parse_octets(usize::MAX).is_err());
+        assert_eq!(parser.pos(), 4);
         assert_eq!(parser.parse_octets(6).unwrap(), b"456789");
     }
 
@@ -802,6 +1957,143 @@ mod test {
         assert!(parser.parse_u128_le().is_err());
     }
 
+    #[test]
+    fn parse_back() {
+        let mut parser = Parser::from_static(
+            b"\x01\x02header-trailer\x12\x34"
+        );
+        assert_eq!(parser.parse_u16_be(), Ok(0x0102));
+        assert_eq!(parser.parse_u16_le_back(), Ok(0x3412));
+        assert_eq!(parser.len(), 16);
+        assert_eq!(parser.parse_octets_back(7).unwrap(), b"trailer");
+        assert_eq!(parser.parse_u8_back(), Ok(b'-'));
+        assert_eq!(parser.peek_all(), b"header");
+        assert_eq!(parser.peek_back(2), Ok(b"er".as_ref()));
+        assert!(parser.parse_u64_be_back().is_err());
+        assert_eq!(parser.len(), 8);
+        assert_eq!(parser.parse_octets(6).unwrap(), b"header");
+        assert!(parser.parse_u8_back().is_err());
+        assert_eq!(parser.remaining(), 0);
+    }
+
+    #[test]
+    fn parse_str() {
+        fn invalid_offset(err: ParseStrError) -> usize {
+            match err {
+                ParseStrError::InvalidUtf8 { offset, .. } => offset,
+                _ => panic!("expected invalid UTF-8")
+            }
+        }
+
+        let mut parser = Parser::from_static(
+            b"ab\x03c\xc3\xbc\x00\x02de\x01\x00f\xffgh\x00\x04i\xc3"
+        );
+        assert_eq!(parser.parse_str(2).unwrap(), "ab");
+        assert_eq!(parser.parse_str_u8_prefixed().unwrap(), "cü");
+        assert_eq!(parser.parse_str_u16_be_prefixed().unwrap(), "de");
+        assert_eq!(parser.parse_str_u16_le_prefixed().unwrap(), "f");
+        assert_eq!(
+            invalid_offset(parser.parse_str_nul_terminated().unwrap_err()),
+            13
+        );
+        assert_eq!(parser.pos(), 13);
+        parser.advance(1).unwrap();
+        assert_eq!(parser.parse_str_nul_terminated().unwrap(), "gh");
+        assert!(parser.parse_str_u8_prefixed().unwrap_err().is_short_input());
+        assert_eq!(parser.pos(), 17);
+        parser.advance(1).unwrap();
+        assert_eq!(invalid_offset(parser.parse_str(2).unwrap_err()), 19);
+        assert!(
+            parser.parse_str_nul_terminated().unwrap_err().is_short_input()
+        );
+        assert_eq!(parser.pos(), 18);
+    }
+
+    #[test]
+    fn parse_parser_back() {
+        let mut parser = Parser::from_static(b"0123456789");
+        parser.advance(2).unwrap();
+        let mut back = parser.parse_parser_back(3).unwrap();
+        assert_eq!(back.peek_all(), b"789");
+        assert_eq!(back.parse_u8(), Ok(b'7'));
+        assert_eq!(parser.peek_all(), b"23456");
+        assert!(parser.parse_parser_back(6).is_err());
+        assert_eq!(parser.retreat_end(5), Ok(()));
+        assert_eq!(parser.remaining(), 0);
+        assert!(parser.retreat_end(1).is_err());
+        assert_eq!(parser.len(), 2);
+    }
+
+    #[test]
+    fn parser_mut_parse() {
+        let mut data = *b"\x12\x34\x56\x78\x9a";
+        let mut parser = ParserMut::from_mut(&mut data);
+        assert_eq!(parser.parse_u8(), Ok(0x12));
+        assert_eq!(parser.parse_u16_be(), Ok(0x3456));
+        assert!(parser.parse_u32_le().is_err());
+        assert_eq!(parser.pos(), 3);
+        assert_eq!(parser.parse_slice(2), Ok(b"\x78\x9a".as_ref()));
+        assert!(parser.parse_u8().is_err());
+    }
+
+    #[test]
+    fn parser_mut_write() {
+        let mut data = *b"0123456789";
+        let mut parser = ParserMut::from_mut(&mut data);
+        parser.advance(1).unwrap();
+        assert_eq!(parser.write_u16_be(0x4142), Ok(()));
+        assert_eq!(parser.pos(), 3);
+        assert_eq!(parser.write_slice(b"xyz"), Ok(()));
+        assert_eq!(parser.write_u32_le(0x64636261), Ok(()));
+        assert_eq!(parser.remaining(), 0);
+        assert!(parser.write_u8(0).is_err());
+        assert_eq!(&data, b"0ABxyzabcd");
+    }
+
+    #[test]
+    fn parser_mut_write_short() {
+        let mut data = *b"0123456789";
+        let mut parser = ParserMut::with_range(&mut data, 2..6);
+        assert!(parser.write_u64_be(0).is_err());
+        assert_eq!(parser.pos(), 2);
+        assert_eq!(parser.peek_all(), b"2345");
+        {
+            let mut sub = parser.parse_parser(2).unwrap();
+            assert!(sub.write_slice(b"abc").is_err());
+            assert_eq!(sub.write_slice(b"ab"), Ok(()));
+        }
+        assert_eq!(parser.pos(), 4);
+        parser.peek_all_mut().fill(b'-');
+        let parser = parser.into_parser();
+        assert_eq!(parser.pos(), 4);
+        assert_eq!(parser.as_slice(), b"01ab--");
+        assert_eq!(&data, b"01ab--6789");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn debug() {
+        use alloc::format;
+
+        let mut parser = Parser::from_static(
+            b"0123456789abcdef0123456789ABCDEF0123456789abcdef\
+              0123456789ABCDEF0123456789abcdef0123456789ABCDEF"
+        );
+        parser.advance(0x32).unwrap();
+        assert_eq!(
+            format!("{:?}", parser),
+            "Parser { pos: 50, len: 96, next: \
+             32 33 34 35 36 37 38 39 41 42 43 44 45 46 30 31 }"
+        );
+        let window = format!("{:#?}", parser);
+        assert!(window.starts_with("Parser {\n    pos: 50,\n    len: 96,\n"));
+        assert!(window.contains("window:\n        00000010  "));
+        assert!(window.contains("\n        00000030  30 31[32]33"));
+        assert!(window.contains("\n        00000050  "));
+        assert!(!window.contains("00000000  "));
+        assert!(window.ends_with("|0123456789ABCDEF|\n}"));
+    }
+
     #[test]
     fn with_range() {
         let range = [0, 1, 2, 3, 4, 5_usize];
@@ -0,0 +1,155 @@
+//! Proptest support.
+//!
+//! If the `proptest` feature is enabled, the crate implements
+//! [proptest](https://crates.io/crates/proptest)’s `Arbitrary` trait for
+//! [`Array`][crate::Array], [`Str`][crate::Str], and
+//! [`StrBuilder`][crate::StrBuilder].
+//!
+//! Since a [`Parser`] only borrows its octets, a strategy can’t produce
+//! parsers directly. Instead, the function [`parser_input`] provides a
+//! strategy for [`ParserInput`] values that own the octets as well as the
+//! range and position of a parser and can hand out parsers atop them:
+//!
+//! ```
+//! use octseq::proptest::parser_input;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(
+//!     input in parser_input(proptest::collection::vec(any::<u8>(), 0..64))
+//! )| {
+//!     let parser = input.parser();
+//!     prop_assert_eq!(parser.remaining(), input.range().end - input.pos());
+//! });
+//! ```
+#![cfg(feature = "proptest")]
+
+use core::ops::Range;
+use proptest::strategy::{Just, Strategy};
+use crate::parse::Parser;
+
+
+//------------ parser_input --------------------------------------------------
+
+/// Returns a strategy for parsers atop the octets of the given strategy.
+///
+/// For each octets sequence generated by `octets`, the strategy picks a
+/// random range within the octets and a random position within that
+/// range.
+pub fn parser_input<S>(
+    octets: S
+) -> impl Strategy<Value = ParserInput<S::Value>>
+where
+    S: Strategy,
+    S::Value: AsRef<[u8]> + Clone,
+{
+    octets.prop_flat_map(|octets| {
+        let len = octets.as_ref().len();
+        (Just(octets), 0..=len, 0..=len, 0..=len)
+    }).prop_map(|(octets, a, b, c)| {
+        let mut idx = [a, b, c];
+        idx.sort_unstable();
+        ParserInput { octets, start: idx[0], pos: idx[1], end: idx[2] }
+    })
+}
+
+
+//------------ ParserInput ---------------------------------------------------
+
+/// An octets sequence with the range and position of a parser.
+///
+/// Values of this type are generated by the strategy returned from
+/// [`parser_input`].
+#[derive(Clone, Debug)]
+pub struct ParserInput<Octs> {
+    /// The underlying octets.
+    octets: Octs,
+
+    /// The start of the parser’s range.
+    start: usize,
+
+    /// The parser’s position.
+    pos: usize,
+
+    /// The end of the parser’s range.
+    end: usize,
+}
+
+impl<Octs> ParserInput<Octs> {
+    /// Returns a reference to the underlying octets.
+    pub fn octets(&self) -> &Octs {
+        &self.octets
+    }
+
+    /// Converts the value into the underlying octets.
+    pub fn into_octets(self) -> Octs {
+        self.octets
+    }
+
+    /// Returns the range of the octets the parser is limited to.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the position of the parser.
+    ///
+    /// Like [`Parser::pos`], this is an index into the complete octets.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a parser atop the octets.
+    ///
+    /// The parser is limited to [`range`][Self::range] and positioned at
+    /// [`pos`][Self::pos].
+    pub fn parser(&self) -> Parser<'_, Octs>
+    where Octs: AsRef<[u8]> {
+        let mut res = Parser::with_range(&self.octets, self.range());
+        res.advance(self.pos - self.start).expect("position out of range");
+        res
+    }
+}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use crate::array::Array;
+    use crate::str::{Str, StrBuilder};
+
+    proptest! {
+        #[test]
+        fn parser(
+            input in parser_input(
+                proptest::collection::vec(any::<u8>(), 0..32)
+            )
+        ) {
+            let range = input.range();
+            let parser = input.parser();
+            prop_assert!(range.start <= input.pos());
+            prop_assert!(input.pos() <= range.end);
+            prop_assert!(range.end <= input.octets().len());
+            prop_assert_eq!(parser.pos(), input.pos());
+            prop_assert_eq!(
+                parser.peek_all(), &input.octets()[input.pos()..range.end]
+            );
+        }
+
+        #[test]
+        fn array(array in any::<Array<4, u8>>()) {
+            prop_assert!(array.len() <= 4);
+        }
+
+        #[test]
+        fn str(
+            s in any::<Str<Array<4>>>(),
+            builder in any::<StrBuilder<Vec<u8>>>(),
+        ) {
+            prop_assert!(s.len() <= 4);
+            prop_assert!(core::str::from_utf8(s.as_slice()).is_ok());
+            prop_assert!(core::str::from_utf8(builder.as_slice()).is_ok());
+        }
+    }
+}
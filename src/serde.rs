@@ -6,11 +6,42 @@
 //! order to allow octets sequences their own native serialization, the crate
 //! defines two traits [`SerializeOctets`] and [`DeserializeOctets`] if
 //! built with the `serde` feature enabled.
+//!
+//! In human-readable formats such as JSON, octets serialized as bytes
+//! typically end up as an array of numbers. The modules [`hex`],
+//! [`base64`], and [`bytes`] allow choosing the representation of a field
+//! via serde’s `with` attribute:
+//!
+//! ```
+//! # #[cfg(all(feature = "serde", feature = "alloc"))] {
+//! use octseq::Array;
+//!
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Key {
+//!     #[serde(with = "octseq::serde::hex")]
+//!     id: Array<8>,
+//!     #[serde(with = "octseq::serde::base64")]
+//!     secret: Vec<u8>,
+//! }
+//! # }
+//! ```
+//!
+//! The hex and base64 representations are only used if the serializer or
+//! deserializer are human-readable. Otherwise, the octets are serialized
+//! as bytes.
+//!
+//! Finally, [`deserialize_in_place`] deserializes octets into an existing
+//! octets builder, allowing to reuse its allocation.
 #![cfg(feature = "serde")]
 
 use core::fmt;
 use core::marker::PhantomData;
 use serde::de::Visitor;
+use crate::builder::{
+    EmptyBuilder, FreezeBuilder, OctetsBuilder, ShortBuf, Truncate
+};
+use crate::encoding::DecodeError;
+use crate::str::Str;
 
 
 //------------ SerializeOctets -----------------------------------------------
@@ -33,7 +64,7 @@ impl SerializeOctets for [u8] {
     }
 }
 
-impl<'a> SerializeOctets for &'a [u8] {
+impl SerializeOctets for &[u8] {
     fn serialize_octets<S: serde::Serializer>(
         &self, serializer: S
     ) -> Result<S::Ok, S::Error> {
@@ -60,7 +91,7 @@ impl SerializeOctets for alloc::vec::Vec<u8> {
 }
 
 #[cfg(feature = "bytes")]
-impl SerializeOctets for bytes::Bytes {
+impl SerializeOctets for ::bytes::Bytes {
     fn serialize_octets<S: serde::Serializer>(
         &self, serializer: S
     ) -> Result<S::Ok, S::Error> {
@@ -155,7 +186,7 @@ impl<'de> DeserializeOctets<'de> for &'de [u8] {
 
 #[cfg(feature = "alloc")]
 impl<'de> DeserializeOctets<'de> for alloc::borrow::Cow<'de, [u8]> {
-    type Visitor = BorrowedVisitor<Self>;
+    type Visitor = CowVisitor;
 
     fn deserialize_octets<D: serde::Deserializer<'de>>(
         deserializer: D,
@@ -175,7 +206,7 @@ impl<'de> DeserializeOctets<'de> for alloc::borrow::Cow<'de, [u8]> {
     }
 
     fn visitor() -> Self::Visitor {
-        BorrowedVisitor::new()
+        CowVisitor
     }
 }
 
@@ -206,7 +237,7 @@ impl<'de> DeserializeOctets<'de> for alloc::vec::Vec<u8> {
 }
 
 #[cfg(feature = "bytes")]
-impl<'de> DeserializeOctets<'de> for bytes::Bytes {
+impl<'de> DeserializeOctets<'de> for ::bytes::Bytes {
     type Visitor = BufVisitor<Self>;
 
     fn deserialize_octets<D: serde::Deserializer<'de>>(
@@ -286,8 +317,306 @@ impl<'de, const N: usize> DeserializeOctets<'de> for heapless::Vec<u8, N> {
     }
 }
 
+//------------ deserialize_in_place ------------------------------------------
+
+/// Deserializes octets into an existing octets builder.
+///
+/// The function clears `place` and then appends the deserialized octets
+/// to it. This is similar to serde’s
+/// [`Deserialize::deserialize_in_place`][serde::Deserialize] and allows
+/// reusing the allocation of a `Vec<u8>` or `BytesMut` or deserializing
+/// into an `Array<_>` without going through a temporary value.
+///
+/// In addition to bytes, the function accepts strings, using their UTF-8
+/// encoding as the octets, and sequences of `u8`. If the builder runs out
+/// of space, the function returns a custom error created from
+/// [`ShortBuf`]. In this case and if deserialization fails for any other
+/// reason, `place` may contain some of the octets.
+pub fn deserialize_in_place<'de, D, Target>(
+    deserializer: D,
+    place: &mut Target,
+) -> Result<(), D::Error>
+where
+    D: serde::Deserializer<'de>,
+    Target: OctetsBuilder + Truncate + ?Sized,
+{
+    place.truncate(0);
+    deserializer.deserialize_bytes(InPlaceVisitor(place))
+}
+
+
+//------------ hex -----------------------------------------------------------
+
+/// Serialization of octets as hex strings in human-readable formats.
+///
+/// This module is intended to be used via serde’s `with` attribute. If
+/// the serializer is human-readable, the octets are serialized as a string
+/// of lowercase hex digits. Otherwise they are serialized as bytes.
+///
+/// When deserializing from a human-readable format, both upper and lower
+/// case hex digits are accepted.
+pub mod hex {
+    use crate::builder::{EmptyBuilder, FromBuilder};
+    use super::SerializeOctets;
+
+    /// Serializes an octets sequence.
+    pub fn serialize<T, S>(
+        value: &T, serializer: S
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: SerializeOctets + AsRef<[u8]> + ?Sized,
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&crate::encoding::hex(value))
+        }
+        else {
+            value.serialize_octets(serializer)
+        }
+    }
+
+    /// Deserializes an octets sequence.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: super::DeserializeOctets<'de> + FromBuilder,
+        T::Builder: EmptyBuilder,
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(
+                super::DecodingVisitor::<T::Builder>::new(
+                    super::Encoding::Hex
+                )
+            )
+        }
+        else {
+            T::deserialize_octets(deserializer)
+        }
+    }
+}
+
+
+//------------ base64 --------------------------------------------------------
+
+/// Serialization of octets as base64 strings in human-readable formats.
+///
+/// This module is intended to be used via serde’s `with` attribute. If
+/// the serializer is human-readable, the octets are serialized as a
+/// string using the standard base64 alphabet with padding. Otherwise they
+/// are serialized as bytes.
+///
+/// When deserializing from a human-readable format, padding is optional.
+pub mod base64 {
+    use crate::builder::{EmptyBuilder, FromBuilder};
+    use super::SerializeOctets;
+
+    /// Serializes an octets sequence.
+    pub fn serialize<T, S>(
+        value: &T, serializer: S
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: SerializeOctets + AsRef<[u8]> + ?Sized,
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&crate::encoding::base64(value))
+        }
+        else {
+            value.serialize_octets(serializer)
+        }
+    }
+
+    /// Deserializes an octets sequence.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: super::DeserializeOctets<'de> + FromBuilder,
+        T::Builder: EmptyBuilder,
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(
+                super::DecodingVisitor::<T::Builder>::new(
+                    super::Encoding::Base64
+                )
+            )
+        }
+        else {
+            T::deserialize_octets(deserializer)
+        }
+    }
+}
+
+
+//------------ bytes ---------------------------------------------------------
+
+/// Serialization of octets as bytes.
+///
+/// This module is intended to be used via serde’s `with` attribute. It
+/// always serializes octets as bytes, no matter whether the serializer is
+/// human-readable or not. It can be used on fields of types that don’t
+/// implement `Serialize` and `Deserialize` themselves.
+pub mod bytes {
+    use super::{DeserializeOctets, SerializeOctets};
+
+    /// Serializes an octets sequence.
+    pub fn serialize<T, S>(
+        value: &T, serializer: S
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: SerializeOctets + ?Sized,
+        S: serde::Serializer,
+    {
+        value.serialize_octets(serializer)
+    }
+
+    /// Deserializes an octets sequence.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: DeserializeOctets<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize_octets(deserializer)
+    }
+}
+
+
+//------------ DecodingVisitor -----------------------------------------------
+
+/// The encodings supported by [`DecodingVisitor`].
+#[derive(Clone, Copy)]
+enum Encoding {
+    Hex,
+    Base64,
+}
+
+/// A visitor that decodes a string into an octets builder.
+///
+/// The octets are decoded directly into a new, empty builder which is
+/// then frozen. This doesn’t require an allocator if the builder doesn’t.
+struct DecodingVisitor<Builder> {
+    encoding: Encoding,
+    marker: PhantomData<Builder>,
+}
+
+impl<Builder> DecodingVisitor<Builder> {
+    fn new(encoding: Encoding) -> Self {
+        DecodingVisitor { encoding, marker: PhantomData }
+    }
+}
+
+impl<'de, Builder> Visitor<'de> for DecodingVisitor<Builder>
+where Builder: OctetsBuilder + EmptyBuilder + FreezeBuilder {
+    type Value = Builder::Octets;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.encoding {
+            Encoding::Hex => f.write_str("a hex encoded string"),
+            Encoding::Base64 => f.write_str("a base64 encoded string"),
+        }
+    }
+
+    fn visit_str<E: serde::de::Error>(
+        self,
+        value: &str,
+    ) -> Result<Self::Value, E> {
+        let mut builder = Builder::empty();
+        let res = match self.encoding {
+            Encoding::Hex => crate::encoding::decode_hex(value, &mut builder),
+            Encoding::Base64 => {
+                crate::encoding::decode_base64(value, &mut builder)
+            }
+        };
+        match res {
+            Ok(()) => Ok(builder.freeze()),
+            Err(DecodeError::ShortBuf) => Err(E::custom(ShortBuf)),
+            Err(DecodeError::Invalid(_)) => {
+                Err(E::invalid_value(
+                    serde::de::Unexpected::Str(value), &self
+                ))
+            }
+        }
+    }
+}
+
+
+//------------ StrVisitor ----------------------------------------------------
+
+/// A visitor for strings atop octets sequences.
+///
+/// The visitor wraps the visitor of the octets type and forwards all
+/// values to it. Since the octets visitor may be implemented outside of
+/// this crate, the resulting octets are always checked to be valid UTF-8,
+/// even if they were created from a string.
+pub(crate) struct StrVisitor<V>(pub V);
+
+impl<'de, V> Visitor<'de> for StrVisitor<V>
+where
+    V: Visitor<'de>,
+    V::Value: AsRef<[u8]>,
+{
+    type Value = Str<V::Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: serde::de::Error>(
+        self, value: &str,
+    ) -> Result<Self::Value, E> {
+        Str::from_utf8(self.0.visit_str(value)?).map_err(E::custom)
+    }
+
+    fn visit_borrowed_str<E: serde::de::Error>(
+        self, value: &'de str,
+    ) -> Result<Self::Value, E> {
+        Str::from_utf8(self.0.visit_borrowed_str(value)?).map_err(E::custom)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_string<E: serde::de::Error>(
+        self, value: alloc::string::String,
+    ) -> Result<Self::Value, E> {
+        Str::from_utf8(self.0.visit_string(value)?).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(
+        self, value: &[u8],
+    ) -> Result<Self::Value, E> {
+        Str::from_utf8(self.0.visit_bytes(value)?).map_err(E::custom)
+    }
+
+    fn visit_borrowed_bytes<E: serde::de::Error>(
+        self, value: &'de [u8],
+    ) -> Result<Self::Value, E> {
+        Str::from_utf8(
+            self.0.visit_borrowed_bytes(value)?
+        ).map_err(E::custom)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_byte_buf<E: serde::de::Error>(
+        self, value: alloc::vec::Vec<u8>,
+    ) -> Result<Self::Value, E> {
+        Str::from_utf8(self.0.visit_byte_buf(value)?).map_err(E::custom)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self, seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        Str::from_utf8(
+            self.0.visit_seq(seq)?
+        ).map_err(serde::de::Error::custom)
+    }
+}
+
+
 //------------ BorrowedVisitor -------------------------------------------
 
+/// A visitor for octets sequences borrowing from the deserializer.
+///
+/// In addition to borrowed bytes, the visitor accepts borrowed strings,
+/// using their UTF-8 encoding as the octets. Since the octets have to be
+/// borrowed, the visitor cannot accept sequences of `u8`.
 pub struct BorrowedVisitor<T>(PhantomData<T>);
 
 impl<T> BorrowedVisitor<T> {
@@ -322,10 +651,102 @@ where
     ) -> Result<Self::Value, E> {
         Ok(value.into())
     }
+
+    fn visit_borrowed_str<E: serde::de::Error>(
+        self,
+        value: &'de str,
+    ) -> Result<Self::Value, E> {
+        Ok(value.as_bytes().into())
+    }
+}
+
+//------------ CowVisitor ----------------------------------------------------
+
+/// A visitor for a `Cow<[u8]>`.
+///
+/// The visitor borrows the octets if the deserializer provides borrowed
+/// bytes or strings. Otherwise, it accepts transient or owned bytes,
+/// strings, and sequences of `u8` and creates an owned value from them.
+#[cfg(feature = "alloc")]
+pub struct CowVisitor;
+
+#[cfg(feature = "alloc")]
+impl CowVisitor {
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<alloc::borrow::Cow<'de, [u8]>, D::Error> {
+        deserializer.deserialize_bytes(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> serde::de::Visitor<'de> for CowVisitor {
+    type Value = alloc::borrow::Cow<'de, [u8]>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an octet sequence")
+    }
+
+    fn visit_borrowed_bytes<E: serde::de::Error>(
+        self,
+        value: &'de [u8],
+    ) -> Result<Self::Value, E> {
+        Ok(alloc::borrow::Cow::Borrowed(value))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(
+        self,
+        value: &[u8],
+    ) -> Result<Self::Value, E> {
+        Ok(alloc::borrow::Cow::Owned(value.into()))
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(
+        self,
+        value: alloc::vec::Vec<u8>,
+    ) -> Result<Self::Value, E> {
+        Ok(alloc::borrow::Cow::Owned(value))
+    }
+
+    fn visit_borrowed_str<E: serde::de::Error>(
+        self,
+        value: &'de str,
+    ) -> Result<Self::Value, E> {
+        self.visit_borrowed_bytes(value.as_bytes())
+    }
+
+    fn visit_str<E: serde::de::Error>(
+        self,
+        value: &str,
+    ) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+
+    fn visit_string<E: serde::de::Error>(
+        self,
+        value: alloc::string::String,
+    ) -> Result<Self::Value, E> {
+        self.visit_byte_buf(value.into_bytes())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        BufVisitor::<alloc::vec::Vec<u8>>::new().visit_seq(seq).map(
+            alloc::borrow::Cow::Owned
+        )
+    }
 }
 
+
 //------------ BufVisitor ------------------------------------------------
 
+/// A visitor for octets sequences created from a `Vec<u8>`.
+///
+/// In addition to bytes, the visitor accepts strings, using their UTF-8
+/// encoding as the octets, and sequences of `u8`.
 #[cfg(feature = "alloc")]
 pub struct BufVisitor<T>(PhantomData<T>);
 
@@ -364,17 +785,102 @@ where
         Ok(alloc::vec::Vec::from(value).into())
     }
 
+    fn visit_bytes<E: serde::de::Error>(
+        self,
+        value: &[u8],
+    ) -> Result<Self::Value, E> {
+        Ok(alloc::vec::Vec::from(value).into())
+    }
+
     fn visit_byte_buf<E: serde::de::Error>(
         self,
         value: alloc::vec::Vec<u8>,
     ) -> Result<Self::Value, E> {
         Ok(value.into())
     }
+
+    fn visit_str<E: serde::de::Error>(
+        self,
+        value: &str,
+    ) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+
+    fn visit_string<E: serde::de::Error>(
+        self,
+        value: alloc::string::String,
+    ) -> Result<Self::Value, E> {
+        self.visit_byte_buf(value.into_bytes())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        // Don’t trust the size hint too much.
+        let mut res = alloc::vec::Vec::with_capacity(
+            seq.size_hint().unwrap_or(0).min(4096)
+        );
+        while let Some(octet) = seq.next_element()? {
+            res.push(octet)
+        }
+        Ok(res.into())
+    }
+}
+
+
+//------------ InPlaceVisitor ------------------------------------------------
+
+/// A visitor appending octets to an octets builder.
+///
+/// This is the visitor used by [`deserialize_in_place`]. It appends all
+/// octets it receives to the builder without clearing it first.
+pub struct InPlaceVisitor<'a, Target: ?Sized>(pub &'a mut Target);
+
+impl<'de, Target> Visitor<'de> for InPlaceVisitor<'_, Target>
+where Target: OctetsBuilder + ?Sized {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an octet sequence")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(
+        self,
+        value: &[u8],
+    ) -> Result<Self::Value, E> {
+        self.0.append_slice(value).map_err(|err| {
+            E::custom(Into::<ShortBuf>::into(err))
+        })
+    }
+
+    fn visit_str<E: serde::de::Error>(
+        self,
+        value: &str,
+    ) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        while let Some(octet) = seq.next_element::<u8>()? {
+            self.0.append_slice(&[octet]).map_err(|err| {
+                serde::de::Error::custom(Into::<ShortBuf>::into(err))
+            })?;
+        }
+        Ok(())
+    }
 }
 
 
 //------------ HeaplessVisitor -----------------------------------------------
 
+/// A visitor for `heapless::Vec<u8, N>`.
+///
+/// In addition to bytes, the visitor accepts strings, using their UTF-8
+/// encoding as the octets, and sequences of `u8`.
 #[cfg(feature = "heapless")]
 pub struct HeaplessVecVisitor<const N: usize>;
 
@@ -398,7 +904,7 @@ impl<'de, const N: usize> serde::de::Visitor<'de> for HeaplessVecVisitor<N> {
 
     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_fmt(format_args!(
-            "an octet sequence of length {} of shorter",
+            "an octet sequence of length {} or shorter",
             N
         ))
     }
@@ -413,5 +919,355 @@ impl<'de, const N: usize> serde::de::Visitor<'de> for HeaplessVecVisitor<N> {
 
         Ok(heapless::Vec::from_iter(value.iter().copied()))
     }
+
+    fn visit_str<E: serde::de::Error>(
+        self,
+        value: &str,
+    ) -> Result<Self::Value, E> {
+        self.visit_bytes(value.as_bytes())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut res = heapless::Vec::new();
+        while let Some(octet) = seq.next_element()? {
+            if res.push(octet).is_err() {
+                return Err(serde::de::Error::invalid_length(N + 1, &self))
+            }
+        }
+        Ok(res)
+    }
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test {
+    use alloc::vec::Vec;
+    use serde_test::{
+        Configure, Token, assert_de_tokens, assert_de_tokens_error,
+        assert_ser_tokens, assert_tokens,
+    };
+    use crate::array::Array;
+    use crate::str::AsciiStr;
+
+    #[derive(
+        Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize
+    )]
+    struct Hex(#[serde(with = "super::hex")] Array<4>);
+
+    #[derive(
+        Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize
+    )]
+    struct Base64(#[serde(with = "super::base64")] Vec<u8>);
+
+    #[derive(
+        Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize
+    )]
+    struct Bytes(#[serde(with = "super::bytes")] Vec<u8>);
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+    struct ArrayBytes(#[serde(with = "super::bytes")] Array<2>);
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+    struct Borrowed<'a>(#[serde(with = "super::bytes", borrow)] &'a [u8]);
+
+    #[test]
+    fn hex() {
+        let value = Hex(Array::try_from(b"\x01\xab".as_ref()).unwrap());
+        assert_tokens(&value.clone().readable(), &[
+            Token::NewtypeStruct { name: "Hex" }, Token::Str("01ab")
+        ]);
+        assert_tokens(&value.compact(), &[
+            Token::NewtypeStruct { name: "Hex" }, Token::Bytes(b"\x01\xab")
+        ]);
+        assert_de_tokens_error::<serde_test::Readable<Hex>>(
+            &[Token::NewtypeStruct { name: "Hex" }, Token::Str("01abcdef01")],
+            "buffer size exceeded"
+        );
+        assert_de_tokens_error::<serde_test::Readable<Hex>>(
+            &[Token::NewtypeStruct { name: "Hex" }, Token::Str("0g")],
+            "invalid value: string \"0g\", expected a hex encoded string"
+        );
+    }
+
+    #[test]
+    fn base64() {
+        let value = Base64(b"foob".into());
+        assert_tokens(&value.clone().readable(), &[
+            Token::NewtypeStruct { name: "Base64" }, Token::Str("Zm9vYg==")
+        ]);
+        assert_ser_tokens(&value.clone().compact(), &[
+            Token::NewtypeStruct { name: "Base64" }, Token::Bytes(b"foob")
+        ]);
+        assert_de_tokens(&value.compact(), &[
+            Token::NewtypeStruct { name: "Base64" }, Token::ByteBuf(b"foob")
+        ]);
+    }
+
+    #[test]
+    fn bytes() {
+        let value = Bytes(b"foob".into());
+        assert_ser_tokens(&value.clone().readable(), &[
+            Token::NewtypeStruct { name: "Bytes" }, Token::Bytes(b"foob")
+        ]);
+        assert_de_tokens(&value.readable(), &[
+            Token::NewtypeStruct { name: "Bytes" }, Token::ByteBuf(b"foob")
+        ]);
+    }
+
+    #[test]
+    fn visit_seq_and_str() {
+        let seq = [
+            Token::NewtypeStruct { name: "Bytes" },
+            Token::Seq { len: Some(2) }, Token::U8(b'a'), Token::U8(b'b'),
+            Token::SeqEnd,
+        ];
+        assert_de_tokens(&Bytes(b"ab".into()), &seq);
+        assert_de_tokens(&Bytes(b"ab".into()), &[
+            Token::NewtypeStruct { name: "Bytes" }, Token::Str("ab")
+        ]);
+        assert_de_tokens(&Bytes(b"ab".into()), &[
+            Token::NewtypeStruct { name: "Bytes" }, Token::String("ab")
+        ]);
+        assert_de_tokens(&Bytes(b"ab".into()), &[
+            Token::NewtypeStruct { name: "Bytes" }, Token::Bytes(b"ab")
+        ]);
+
+        let array = ArrayBytes(Array::try_from(b"ab".as_ref()).unwrap());
+        assert_de_tokens(&array, &[
+            Token::NewtypeStruct { name: "ArrayBytes" },
+            Token::Seq { len: None }, Token::U8(b'a'), Token::U8(b'b'),
+            Token::SeqEnd,
+        ]);
+        assert_de_tokens(&array, &[
+            Token::NewtypeStruct { name: "ArrayBytes" }, Token::Str("ab")
+        ]);
+        assert_de_tokens_error::<ArrayBytes>(
+            &[
+                Token::NewtypeStruct { name: "ArrayBytes" },
+                Token::Seq { len: None },
+                Token::U8(b'a'), Token::U8(b'b'), Token::U8(b'c'),
+            ],
+            "buffer size exceeded"
+        );
+
+        assert_de_tokens(&Borrowed(b"ab"), &[
+            Token::NewtypeStruct { name: "Borrowed" }, Token::BorrowedStr("ab")
+        ]);
+
+        #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+        struct CowBytes<'a>(
+            #[serde(with = "super::bytes", borrow)]
+            alloc::borrow::Cow<'a, [u8]>
+        );
+
+        assert_de_tokens(&CowBytes(b"ab".as_ref().into()), &[
+            Token::NewtypeStruct { name: "CowBytes" },
+            Token::Seq { len: None }, Token::U8(b'a'), Token::U8(b'b'),
+            Token::SeqEnd,
+        ]);
+        assert_de_tokens(&CowBytes(b"ab".as_ref().into()), &[
+            Token::NewtypeStruct { name: "CowBytes" }, Token::Str("ab")
+        ]);
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn heapless_visit_seq() {
+        #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+        struct Heapless(
+            #[serde(with = "super::bytes")] heapless::Vec<u8, 2>
+        );
+
+        assert_de_tokens(
+            &Heapless(heapless::Vec::from_slice(b"ab").unwrap()),
+            &[
+                Token::NewtypeStruct { name: "Heapless" },
+                Token::Seq { len: None }, Token::U8(b'a'), Token::U8(b'b'),
+                Token::SeqEnd,
+            ]
+        );
+        assert_de_tokens_error::<Heapless>(
+            &[
+                Token::NewtypeStruct { name: "Heapless" },
+                Token::Seq { len: None },
+                Token::U8(b'a'), Token::U8(b'b'), Token::U8(b'c'),
+            ],
+            "invalid length 3, expected an octet sequence of length 2 or \
+             shorter"
+        );
+    }
+
+    #[test]
+    fn str() {
+        use crate::str::{Str, StrBuilder};
+
+        let value = Str::from_string("ab".into());
+        assert_tokens(&value, &[Token::Str("ab")]);
+        assert_de_tokens(&value, &[Token::String("ab")]);
+        assert_de_tokens(&value, &[Token::Bytes(b"ab")]);
+        assert_de_tokens(&value, &[
+            Token::Seq { len: None }, Token::U8(b'a'), Token::U8(b'b'),
+            Token::SeqEnd,
+        ]);
+        assert_de_tokens_error::<Str<Vec<u8>>>(
+            &[Token::Bytes(b"a\xff")],
+            "invalid utf-8 sequence of 1 bytes from index 1"
+        );
+
+        let borrowed: Str<&[u8]> = Str::from_static("ab");
+        assert_tokens(&borrowed, &[Token::BorrowedStr("ab")]);
+        assert_de_tokens(&borrowed, &[Token::BorrowedBytes(b"ab")]);
+        assert_de_tokens_error::<Str<&[u8]>>(
+            &[Token::Str("ab")],
+            "invalid type: string \"ab\", expected an octet sequence"
+        );
+
+        let array = Str::<Array<4>>::try_copy_from_str("ab").unwrap();
+        assert_tokens(&array, &[Token::Str("ab")]);
+        assert_de_tokens_error::<Str<Array<1>>>(
+            &[Token::Str("ab")], "buffer size exceeded"
+        );
+
+        let builder = StrBuilder::<Vec<u8>>::from_utf8(b"ab".into()).unwrap();
+        assert_tokens(&builder, &[Token::Str("ab")]);
+    }
+
+    #[test]
+    fn str_checks_foreign_visitor() {
+        use core::fmt;
+        use crate::str::Str;
+
+        /// Octets whose visitor turns every string into invalid UTF-8.
+        #[derive(Debug)]
+        struct Foreign(Vec<u8>);
+
+        impl AsRef<[u8]> for Foreign {
+            fn as_ref(&self) -> &[u8] {
+                self.0.as_ref()
+            }
+        }
+
+        struct ForeignVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ForeignVisitor {
+            type Value = Foreign;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("anything")
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(Foreign(b"\xff".into()))
+            }
+        }
+
+        impl<'de> super::DeserializeOctets<'de> for Foreign {
+            type Visitor = ForeignVisitor;
+
+            fn deserialize_octets<D: serde::Deserializer<'de>>(
+                deserializer: D
+            ) -> Result<Self, D::Error> {
+                deserializer.deserialize_bytes(ForeignVisitor)
+            }
+
+            fn deserialize_with_visitor<D, V>(
+                deserializer: D, visitor: V,
+            ) -> Result<V::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+                V: serde::de::Visitor<'de>,
+            {
+                deserializer.deserialize_bytes(visitor)
+            }
+
+            fn visitor() -> Self::Visitor {
+                ForeignVisitor
+            }
+        }
+
+        assert_de_tokens_error::<Str<Foreign>>(
+            &[Token::Str("ab")],
+            "invalid utf-8 sequence of 1 bytes from index 0"
+        );
+    }
+
+    #[test]
+    fn ascii_str() {
+        let array = AsciiStr::from_ascii(Array::<4>::from(*b"ab")).unwrap();
+        assert_tokens(&array, &[Token::Str("ab")]);
+        assert_de_tokens(&array, &[Token::Bytes(b"ab")]);
+        assert_de_tokens_error::<AsciiStr<Array<4>>>(
+            &[Token::Str("\u{fc}")],
+            "non-ASCII octet at index 0"
+        );
+
+        let borrowed = AsciiStr::from_ascii(b"ab".as_ref()).unwrap();
+        assert_tokens(&borrowed, &[Token::BorrowedStr("ab")]);
+        assert_de_tokens(&borrowed, &[Token::BorrowedBytes(b"ab")]);
+
+        let vec = AsciiStr::from_ascii(Vec::from(*b"ab")).unwrap();
+        assert_ser_tokens(&vec, &[Token::Str("ab")]);
+        assert_de_tokens(&vec, &[Token::String("ab")]);
+        assert_de_tokens(&vec, &[Token::BorrowedStr("ab")]);
+
+        let builder = crate::str::AsciiStrBuilder::from_ascii(
+            Vec::from(*b"ab")
+        ).unwrap();
+        assert_ser_tokens(&builder, &[Token::Str("ab")]);
+        assert_de_tokens(&builder, &[Token::String("ab")]);
+    }
+
+    #[test]
+    fn in_place() {
+        use alloc::string::ToString;
+        use serde::de::value::{
+            BytesDeserializer, Error, SeqDeserializer, StrDeserializer,
+        };
+        use super::deserialize_in_place;
+
+        let mut vec = Vec::with_capacity(16);
+        vec.extend_from_slice(b"stale");
+        let ptr = vec.as_ptr();
+        deserialize_in_place(
+            BytesDeserializer::<Error>::new(b"foo"), &mut vec
+        ).unwrap();
+        assert_eq!(vec, b"foo");
+        assert_eq!(vec.as_ptr(), ptr);
+        deserialize_in_place(
+            StrDeserializer::<Error>::new("ba"), &mut vec
+        ).unwrap();
+        assert_eq!(vec, b"ba");
+        deserialize_in_place(
+            SeqDeserializer::<_, Error>::new(b"seq".iter().copied()),
+            &mut vec
+        ).unwrap();
+        assert_eq!(vec, b"seq");
+
+        let mut array = Array::<4>::try_from(b"old".as_ref()).unwrap();
+        deserialize_in_place(
+            BytesDeserializer::<Error>::new(b"new!"), &mut array
+        ).unwrap();
+        assert_eq!(array.as_slice(), b"new!");
+        assert_eq!(
+            deserialize_in_place(
+                BytesDeserializer::<Error>::new(b"toolong"), &mut array
+            ).unwrap_err().to_string(),
+            "buffer size exceeded"
+        );
+        assert_eq!(
+            deserialize_in_place(
+                SeqDeserializer::<_, Error>::new(
+                    b"toolong".iter().copied()
+                ),
+                &mut array
+            ).unwrap_err().to_string(),
+            "buffer size exceeded"
+        );
+    }
+}
@@ -4,13 +4,29 @@
 //! invariants – namely that the content is an UTF-8 encoded string – as
 //! the standard library’s [`str`] and [`String`] types but atop a generic
 //! octet sequence.
+//!
+//! For building strings without allocating, the module also provides
+//! [`ArrayString<N>`], a string of up to `N` octets atop an
+//! [`Array<N>`][crate::array::Array].
+//!
+//! Finally, the types [`AsciiStr<Octets>`] and [`AsciiStrBuilder<Octets>`]
+//! are restricted to ASCII characters. In addition to byte-exact
+//! comparison, they offer comparison and hashing ignoring ASCII case.
+//! For using any octets sequence or string as a key that ignores ASCII
+//! case, the wrapper [`CaseInsensitive<T>`] is available.
+//!
+//! The functions [`escape`] and [`unescape`] convert between arbitrary
+//! octets and their textual representation using escape sequences in one
+//! of the styles defined by [`EscapeStyle`].
 
 use core::{borrow, cmp, fmt, hash, ops, str};
 use core::convert::Infallible;
+use crate::array::Array;
 use crate::builder::{
     BuilderAppendError, EmptyBuilder, FreezeBuilder, FromBuilder,
-    OctetsBuilder, Truncate, infallible
+    OctetsBuilder, ShortBuf, Truncate, infallible
 };
+use crate::encoding::{DecodeError, append};
 use crate::octets::OctetsFrom;
 
 
@@ -38,7 +54,7 @@ impl<Octets> Str<Octets> {
     ///
     /// The caller must make sure that the contents of `octets` is a
     /// correctly encoded UTF-8 string.
-    pub unsafe fn from_utf8_unchecked(octets: Octets) -> Self {
+    pub const unsafe fn from_utf8_unchecked(octets: Octets) -> Self {
         Self(octets)
     }
 
@@ -71,6 +87,32 @@ impl<Octets> Str<Octets> {
     }
 }
 
+impl Str<&'static [u8]> {
+    /// Creates a string value from a static string slice.
+    ///
+    /// This function can be used in const context, e.g., for defining
+    /// constants or statics.
+    pub const fn from_static(s: &'static str) -> Self {
+        Self(s.as_bytes())
+    }
+
+    /// Creates a string value from a static octets slice.
+    ///
+    /// This function is intended to be used in const context.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `octets` does not contain correctly encoded
+    /// UTF-8. If the function is evaluated in const context, this results
+    /// in a compile time error.
+    pub const fn from_utf8_static(octets: &'static [u8]) -> Self {
+        match str::from_utf8(octets) {
+            Ok(s) => Self::from_static(s),
+            Err(_) => panic!("invalid UTF-8")
+        }
+    }
+}
+
 impl Str<[u8]> {
     /// Creates a string value from a UTF-8 slice.
     pub fn from_utf8_slice(
@@ -84,7 +126,7 @@ impl Str<[u8]> {
 
     /// Creates a string value from a string slice.
     #[allow(clippy::should_implement_trait)]
-    pub fn from_str(s: &str) -> &Self {
+    pub const fn from_str(s: &str) -> &Self {
         unsafe { &*(s as *const str as *const Self) }
     }
 }
@@ -281,6 +323,121 @@ impl<Octets: AsRef<[u8]> + ?Sized> Ord for Str<Octets> {
 }
 
 
+//--- Zeroize and ZeroizeOnDrop
+
+/// Wipes the string by zeroizing the underlying octets.
+///
+/// Zeroizing octets either sets them to zero or removes them altogether.
+/// In both cases the result is still valid UTF-8.
+#[cfg(feature = "zeroize")]
+impl<Octets: zeroize::Zeroize + ?Sized> zeroize::Zeroize for Str<Octets> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Octets> zeroize::ZeroizeOnDrop for Str<Octets>
+where Octets: zeroize::ZeroizeOnDrop + ?Sized { }
+
+
+//--- Serialize and Deserialize
+
+#[cfg(feature = "serde")]
+impl<Octets> serde::Serialize for Str<Octets>
+where Octets: AsRef<[u8]> + ?Sized {
+    fn serialize<S: serde::Serializer>(
+        &self, serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes a string.
+///
+/// If the octets type borrows from the deserializer, such as `&[u8]`, the
+/// string has to be available as a borrowed string or bytes. Otherwise,
+/// the data is copied. If the data is provided as bytes or a sequence of
+/// `u8`, it is checked to be valid UTF-8.
+#[cfg(feature = "serde")]
+impl<'de, Octets> serde::Deserialize<'de> for Str<Octets>
+where Octets: crate::serde::DeserializeOctets<'de> + AsRef<[u8]> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(
+            crate::serde::StrVisitor(Octets::visitor())
+        )
+    }
+}
+
+
+//--- Arbitrary
+
+/// Creates a string from as many characters of an arbitrary string as fit.
+#[cfg(feature = "arbitrary")]
+impl<'a, Octets> arbitrary::Arbitrary<'a> for Str<Octets>
+where
+    Octets: FromBuilder,
+    <Octets as FromBuilder>::Builder: EmptyBuilder,
+{
+    fn arbitrary(
+        u: &mut arbitrary::Unstructured<'a>
+    ) -> arbitrary::Result<Self> {
+        StrBuilder::<Octets::Builder>::arbitrary(u).map(StrBuilder::freeze)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<Octets> proptest::arbitrary::Arbitrary for Str<Octets>
+where
+    Octets: FromBuilder,
+    <Octets as FromBuilder>::Builder: EmptyBuilder,
+{
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        proptest::arbitrary::StrategyFor<std::string::String>,
+        fn(std::string::String) -> Self
+    >;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        proptest::arbitrary::any::<std::string::String>().prop_map(|s| {
+            builder_from_str_truncating::<Octets::Builder>(&s).freeze()
+        })
+    }
+}
+
+
+//------------ static_str! ---------------------------------------------------
+
+/// Creates a static [`Str`] from a string literal in const context.
+///
+/// The macro creates a `Str<&'static [u8]>`. In order to create such a
+/// value from a byte string literal with a compile time check for correct
+/// UTF-8, use [`Str::from_utf8_static`] in a const context.
+///
+/// ```
+/// use octseq::{static_str, Str};
+///
+/// static NAME: Str<&[u8]> = static_str!("example");
+/// const OTHER: Str<&[u8]> = Str::from_utf8_static(b"f\xc3\xbc\xc3\x9f");
+/// assert_eq!(NAME, "example");
+/// assert_eq!(OTHER, "füß");
+/// ```
+#[macro_export]
+macro_rules! static_str {
+    ( $s:expr ) => {
+        {
+            const STR: $crate::Str<&'static [u8]>
+                = $crate::Str::from_static($s);
+            STR
+        }
+    };
+}
+
+
 //------------ StrBuilder ----------------------------------------------------
 
 /// A growable, UTF-8 encoded string atop an octets builder.
@@ -474,6 +631,222 @@ impl<Octets> StrBuilder<Octets> {
         self.truncate(self.len() - ch.len_utf8());
         Some(ch)
     }
+
+    /// Inserts a character at the given octet position.
+    ///
+    /// If the builder cannot grow, returns an error and leaves the builder
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `idx` is larger than the length of the builder
+    /// or does not lie on a `char` boundary.
+    pub fn try_insert(
+        &mut self, idx: usize, ch: char
+    ) -> Result<(), Octets::AppendError>
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + OctetsBuilder + Truncate {
+        self.try_insert_str(idx, ch.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Inserts a character at the given octet position.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `idx` is larger than the length of the builder
+    /// or does not lie on a `char` boundary.
+    pub fn insert(&mut self, idx: usize, ch: char)
+    where
+        Octets: AsRef<[u8]> + AsMut<[u8]> + OctetsBuilder + Truncate,
+        Octets::AppendError: Into<Infallible>
+    {
+        infallible(self.try_insert(idx, ch))
+    }
+
+    /// Inserts a string slice at the given octet position.
+    ///
+    /// If the builder cannot grow, returns an error and leaves the builder
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `idx` is larger than the length of the builder
+    /// or does not lie on a `char` boundary.
+    pub fn try_insert_str(
+        &mut self, idx: usize, s: &str
+    ) -> Result<(), Octets::AppendError>
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + OctetsBuilder + Truncate {
+        self.try_replace_range(idx..idx, s)
+    }
+
+    /// Inserts a string slice at the given octet position.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `idx` is larger than the length of the builder
+    /// or does not lie on a `char` boundary.
+    pub fn insert_str(&mut self, idx: usize, s: &str)
+    where
+        Octets: AsRef<[u8]> + AsMut<[u8]> + OctetsBuilder + Truncate,
+        Octets::AppendError: Into<Infallible>
+    {
+        infallible(self.try_insert_str(idx, s))
+    }
+
+    /// Removes the character at the given octet position and returns it.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `idx` is not smaller than the length of the
+    /// builder or does not lie on a `char` boundary.
+    pub fn remove(&mut self, idx: usize) -> char
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+        let ch = match self.as_str()[idx..].chars().next() {
+            Some(ch) => ch,
+            None => panic!("cannot remove a char from the end of a string"),
+        };
+        self.remove_range(idx, idx + ch.len_utf8());
+        ch
+    }
+
+    /// Replaces the given range of the builder with a string slice.
+    ///
+    /// The range is given in octets. The string slice doesn’t need to be
+    /// of the same length as the range.
+    ///
+    /// If the builder cannot grow, returns an error and leaves the builder
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the start or end of the range are out of
+    /// bounds or do not lie on a `char` boundary.
+    pub fn try_replace_range(
+        &mut self, range: impl ops::RangeBounds<usize>, s: &str
+    ) -> Result<(), Octets::AppendError>
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + OctetsBuilder + Truncate {
+        let (start, end) = self.check_range(range);
+        let range_len = end - start;
+        let s = s.as_bytes();
+        if s.len() > range_len {
+            // Append the surplus at the end and rotate it into place.
+            self.0.append_slice(&s[range_len..])?;
+            let buf = self.0.as_mut();
+            buf[start..end].copy_from_slice(&s[..range_len]);
+            buf[end..].rotate_right(s.len() - range_len);
+        }
+        else {
+            self.0.as_mut()[start..start + s.len()].copy_from_slice(s);
+            remove_octets(&mut self.0, start + s.len(), end);
+        }
+        Ok(())
+    }
+
+    /// Replaces the given range of the builder with a string slice.
+    ///
+    /// The range is given in octets. The string slice doesn’t need to be
+    /// of the same length as the range.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the start or end of the range are out of
+    /// bounds or do not lie on a `char` boundary.
+    pub fn replace_range(
+        &mut self, range: impl ops::RangeBounds<usize>, s: &str
+    )
+    where
+        Octets: AsRef<[u8]> + AsMut<[u8]> + OctetsBuilder + Truncate,
+        Octets::AppendError: Into<Infallible>
+    {
+        infallible(self.try_replace_range(range, s))
+    }
+
+    /// Retains only the characters for which `f` returns `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(char) -> bool)
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+        /// Truncates the octets to the retained part when dropped.
+        ///
+        /// Should `f` panic, everything not processed yet is dropped,
+        /// too, since the octets between `len` and the current position
+        /// may not be valid UTF-8 anymore.
+        struct Guard<'a, Octets: Truncate> {
+            octets: &'a mut Octets,
+            len: usize,
+        }
+
+        impl<Octets: Truncate> Drop for Guard<'_, Octets> {
+            fn drop(&mut self) {
+                self.octets.truncate(self.len)
+            }
+        }
+
+        let end = self.len();
+        let mut guard = Guard { octets: &mut self.0, len: 0 };
+        let mut pos = 0;
+        while pos < end {
+            let ch = unsafe {
+                str::from_utf8_unchecked(&guard.octets.as_ref()[pos..end])
+            }.chars().next().expect("non-empty string");
+            let ch_len = ch.len_utf8();
+            if f(ch) {
+                let len = guard.len;
+                guard.octets.as_mut().copy_within(pos..pos + ch_len, len);
+                guard.len += ch_len;
+            }
+            pos += ch_len;
+        }
+    }
+
+    /// Removes the given range from the builder and returns its characters.
+    ///
+    /// The range is given in octets. The range is removed when the
+    /// returned iterator is dropped, even if it hasn’t been fully
+    /// consumed.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if the start or end of the range are out of
+    /// bounds or do not lie on a `char` boundary.
+    pub fn drain(
+        &mut self, range: impl ops::RangeBounds<usize>
+    ) -> Drain<'_, Octets>
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+        let (start, end) = self.check_range(range);
+        Drain { octets: &mut self.0, start, end, front: start, back: end }
+    }
+
+    /// Checks that a range is within bounds and on character boundaries.
+    ///
+    /// Returns the start and end of the range.
+    fn check_range(
+        &self, range: impl ops::RangeBounds<usize>
+    ) -> (usize, usize)
+    where Octets: AsRef<[u8]> {
+        let start = match range.start_bound() {
+            ops::Bound::Included(&start) => start,
+            ops::Bound::Excluded(&start) => {
+                start.checked_add(1).expect("range start out of bounds")
+            }
+            ops::Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            ops::Bound::Included(&end) => {
+                end.checked_add(1).expect("range end out of bounds")
+            }
+            ops::Bound::Excluded(&end) => end,
+            ops::Bound::Unbounded => self.len(),
+        };
+        assert!(start <= end, "range start is after range end");
+        assert!(end <= self.len(), "range end out of bounds");
+        assert!(self.as_str().is_char_boundary(start));
+        assert!(self.as_str().is_char_boundary(end));
+        (start, end)
+    }
+
+    /// Removes the octets between `start` and `end`.
+    fn remove_range(&mut self, start: usize, end: usize)
+    where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+        remove_octets(&mut self.0, start, end)
+    }
 }
 
 
@@ -588,120 +961,2071 @@ impl<Octets: AsRef<[u8]>> Ord for StrBuilder<Octets> {
 }
 
 
-//============ Error Types ===================================================
-
-//------------ FromUtf8Error -------------------------------------------------
+//--- Zeroize and ZeroizeOnDrop
 
-/// An error happened when converting octets into a string.
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub struct FromUtf8Error<Octets> {
-    octets: Octets,
-    error: str::Utf8Error,
+/// Wipes the string builder by zeroizing the underlying octets builder.
+///
+/// Zeroizing octets either sets them to zero or removes them altogether.
+/// In both cases the result is still valid UTF-8.
+#[cfg(feature = "zeroize")]
+impl<Octets: zeroize::Zeroize> zeroize::Zeroize for StrBuilder<Octets> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
 }
 
-impl<Octets> FromUtf8Error<Octets> {
-    /// Returns an octets slice of the data that failed to convert.
-    pub fn as_slice(&self) -> &[u8]
-    where Octets: AsRef<[u8]> {
-        self.octets.as_ref()
-    }
+#[cfg(feature = "zeroize")]
+impl<Octets> zeroize::ZeroizeOnDrop for StrBuilder<Octets>
+where Octets: zeroize::ZeroizeOnDrop { }
 
-    /// Returns the octets sequence that failed to convert.
-    pub fn into_octets(self) -> Octets {
-        self.octets
+
+//--- Serialize and Deserialize
+
+#[cfg(feature = "serde")]
+impl<Octets: AsRef<[u8]>> serde::Serialize for StrBuilder<Octets> {
+    fn serialize<S: serde::Serializer>(
+        &self, serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
     }
+}
 
-    /// Returns the reason for the conversion error.
-    pub fn utf8_error(&self) -> str::Utf8Error {
-        self.error
+#[cfg(feature = "serde")]
+impl<'de, Octets> serde::Deserialize<'de> for StrBuilder<Octets>
+where Octets: crate::serde::DeserializeOctets<'de> + AsRef<[u8]> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        Str::<Octets>::deserialize(deserializer).map(|s| {
+            StrBuilder(s.into_octets())
+        })
     }
 }
 
-impl<Octets> fmt::Debug for FromUtf8Error<Octets> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("FromUtf8Error")
-            .field("error", &self.error)
-            .finish_non_exhaustive()
+
+//--- Arbitrary
+
+/// Creates a builder from as many characters of an arbitrary string as fit.
+#[cfg(feature = "arbitrary")]
+impl<'a, Octets> arbitrary::Arbitrary<'a> for StrBuilder<Octets>
+where Octets: OctetsBuilder + EmptyBuilder {
+    fn arbitrary(
+        u: &mut arbitrary::Unstructured<'a>
+    ) -> arbitrary::Result<Self> {
+        Ok(builder_from_str_truncating(u.arbitrary()?))
     }
 }
 
-impl<Octets> fmt::Display for FromUtf8Error<Octets> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.error, f)
+#[cfg(feature = "proptest")]
+impl<Octets> proptest::arbitrary::Arbitrary for StrBuilder<Octets>
+where Octets: OctetsBuilder + EmptyBuilder + AsRef<[u8]> {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        proptest::arbitrary::StrategyFor<std::string::String>,
+        fn(std::string::String) -> Self
+    >;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        proptest::arbitrary::any::<std::string::String>().prop_map(|s| {
+            builder_from_str_truncating(&s)
+        })
     }
 }
 
-impl<Octets> core::error::Error for FromUtf8Error<Octets> {}
 
+//------------ Drain ---------------------------------------------------------
 
-//============ Testing =======================================================
+/// An iterator over the characters removed from a string builder.
+///
+/// A value of this type is returned by [`StrBuilder::drain`].
+pub struct Drain<'a, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    /// The octets of the string builder.
+    octets: &'a mut Octets,
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// The start of the range to be removed.
+    start: usize,
 
-    // Most of the test cases herein have been borrowed from the test cases
-    // of the Rust standard library.
+    /// The end of the range to be removed.
+    end: usize,
 
-    #[test]
-    #[cfg(feature = "alloc")]
-    fn from_utf8_lossy() {
-        fn check(src: impl AsRef<[u8]>) {
-            assert_eq!(
-                StrBuilder::from_utf8_lossy(alloc::vec::Vec::from(src.as_ref())),
-                alloc::string::String::from_utf8_lossy(src.as_ref())
-            );
+    /// The start of the part of the range not yet iterated over.
+    front: usize,
+
+    /// The end of the part of the range not yet iterated over.
+    back: usize,
+}
+
+impl<Octets> Drain<'_, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    /// Returns the remaining characters as a string slice.
+    pub fn as_str(&self) -> &str {
+        unsafe {
+            str::from_utf8_unchecked(
+                &self.octets.as_ref()[self.front..self.back]
+            )
         }
+    }
+}
 
-        check(b"hello");
-        check("ศไทย中华Việt Nam");
-        check(b"Hello\xC2 There\xFF Goodbye");
-        check(b"Hello\xC0\x80 There\xE6\x83 Goodbye");
-        check(b"\xF5foo\xF5\x80bar");
-        check(b"\xF1foo\xF1\x80bar\xF1\x80\x80baz");
-        check(b"\xF4foo\xF4\x80bar\xF4\xBFbaz");
-        check(b"\xF0\x80\x80\x80foo\xF0\x90\x80\x80bar");
-        check(b"\xED\xA0\x80foo\xED\xBF\xBFbar");
+impl<Octets> Iterator for Drain<'_, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next()?;
+        self.front += ch.len_utf8();
+        Some(ch)
     }
 
-    #[test]
-    #[cfg(feature = "alloc")]
-    fn push_str() {
-        let mut s = StrBuilder::<alloc::vec::Vec<u8>>::new();
-        s.push_str("");
-        assert_eq!(&s[0..], "");
-        s.push_str("abc");
-        assert_eq!(&s[0..], "abc");
-        s.push_str("ประเทศไทย中华Việt Nam");
-        assert_eq!(&s[0..], "abcประเทศไทย中华Việt Nam");
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.as_str().chars().size_hint()
     }
+}
 
-    #[test]
-    #[cfg(feature = "alloc")]
-    fn push() {
-        let mut data = StrBuilder::from_utf8(
-            alloc::vec::Vec::from("ประเทศไทย中".as_bytes())
-        ).unwrap();
-        data.push('华');
-        data.push('b'); // 1 byte
-        data.push('¢'); // 2 byte
-        data.push('€'); // 3 byte
-        data.push('𤭢'); // 4 byte
-        assert_eq!(data, "ประเทศไทย中华b¢€𤭢");
+impl<Octets> DoubleEndedIterator for Drain<'_, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    fn next_back(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next_back()?;
+        self.back -= ch.len_utf8();
+        Some(ch)
     }
+}
 
-    #[test]
-    #[cfg(feature = "alloc")]
-    fn pop() {
-        let mut data = StrBuilder::from_utf8(
-            alloc::vec::Vec::from("ประเทศไทย中华b¢€𤭢".as_bytes())
-        ).unwrap();
-        assert_eq!(data.pop().unwrap(), '𤭢'); // 4 bytes
-        assert_eq!(data.pop().unwrap(), '€'); // 3 bytes
-        assert_eq!(data.pop().unwrap(), '¢'); // 2 bytes
-        assert_eq!(data.pop().unwrap(), 'b'); // 1 bytes
-        assert_eq!(data.pop().unwrap(), '华');
-        assert_eq!(data, "ประเทศไทย中");
+impl<Octets> core::iter::FusedIterator for Drain<'_, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate { }
+
+impl<Octets> Drop for Drain<'_, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    fn drop(&mut self) {
+        remove_octets(self.octets, self.start, self.end)
+    }
+}
+
+impl<Octets> fmt::Debug for Drain<'_, Octets>
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_str()).finish()
     }
 }
 
+
+//------------ ArrayString ---------------------------------------------------
+
+/// A UTF-8 encoded string of up to `N` octets stored inline.
+///
+/// The type is a more convenient alternative to `StrBuilder<Array<N>>`.
+/// Methods that add to the string return [`ShortBuf`] if the result would
+/// exceed the capacity and leave the string unchanged.
+///
+/// For contexts such as logging where losing the end of an overly long
+/// string is preferable to an error, the methods with `truncating` in
+/// their name as well as the [`Truncating`] writer returned by
+/// [`truncating`][Self::truncating] append as much as fits and cut off
+/// the rest at a character boundary.
+#[derive(Clone, Default)]
+pub struct ArrayString<const N: usize>(Array<N>);
+
+impl<const N: usize> ArrayString<N> {
+    /// Creates a new, empty string.
+    pub const fn new() -> Self {
+        Self(Array::new())
+    }
+
+    /// Creates a new string from an array.
+    ///
+    /// Returns an error if the content of the array is not correctly
+    /// encoded UTF-8.
+    pub fn from_utf8(
+        octets: Array<N>
+    ) -> Result<Self, FromUtf8Error<Array<N>>> {
+        if let Err(error) = str::from_utf8(octets.as_slice()) {
+            Err(FromUtf8Error { octets, error })
+        }
+        else {
+            Ok(Self(octets))
+        }
+    }
+
+    /// Creates a new string from an array without checking the content.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the content of `octets` is a correctly
+    /// UTF-8 encoded string.
+    pub unsafe fn from_utf8_unchecked(octets: Array<N>) -> Self {
+        Self(octets)
+    }
+
+    /// Creates a new string from a string slice, truncating if necessary.
+    ///
+    /// If `s` is longer than `N` octets, only as many characters as fit
+    /// are taken.
+    pub fn from_str_truncating(s: &str) -> Self {
+        let mut res = Self::new();
+        res.push_str_truncating(s);
+        res
+    }
+
+    /// Converts the string into the underlying array.
+    pub fn into_array(self) -> Array<N> {
+        self.0
+    }
+
+    /// Converts the string into a `Str` atop the underlying array.
+    pub fn into_str(self) -> Str<Array<N>> {
+        Str(self.0)
+    }
+
+    /// Returns a string slice of the content.
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(self.0.as_slice()) }
+    }
+
+    /// Returns a mutable string slice of the content.
+    pub fn as_str_mut(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(self.0.as_slice_mut()) }
+    }
+
+    /// Returns the string’s octets as a slice.
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Returns the length of the string in octets.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the capacity of the string in octets.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of octets that can still be added.
+    pub fn remaining_capacity(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Appends a string slice to the end of the string.
+    ///
+    /// Returns an error and leaves the string unchanged if `s` doesn’t
+    /// fit.
+    pub fn push_str(&mut self, s: &str) -> Result<(), ShortBuf> {
+        self.0.append_slice(s.as_bytes())
+    }
+
+    /// Appends as much of a string slice as fits.
+    ///
+    /// If `s` is too long, it is cut off at the last character boundary
+    /// that still fits. Returns whether all of `s` was appended.
+    pub fn push_str_truncating(&mut self, s: &str) -> bool {
+        let mut len = self.remaining_capacity();
+        if s.len() <= len {
+            self.0.append_slice(s.as_bytes()).expect("checked length");
+            return true
+        }
+        while !s.is_char_boundary(len) {
+            len -= 1
+        }
+        self.0.append_slice(&s.as_bytes()[..len]).expect("checked length");
+        false
+    }
+
+    /// Appends a character to the end of the string.
+    ///
+    /// Returns an error and leaves the string unchanged if the character
+    /// doesn’t fit.
+    pub fn push(&mut self, ch: char) -> Result<(), ShortBuf> {
+        self.push_str(ch.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Truncates the string, keeping the first `new_len` octets.
+    ///
+    /// # Panics
+    ///
+    /// The method panics if `new_len` does not lie on a `char` boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len() {
+            assert!(self.as_str().is_char_boundary(new_len));
+            self.0.truncate(new_len)
+        }
+    }
+
+    /// Truncates the string to at most `max_len` octets.
+    ///
+    /// Unlike [`truncate`][Self::truncate], this method doesn’t panic if
+    /// `max_len` isn’t on a character boundary but instead removes the
+    /// character that contains it, too.
+    pub fn truncate_floor(&mut self, max_len: usize) {
+        if max_len < self.len() {
+            let mut len = max_len;
+            while !self.as_str().is_char_boundary(len) {
+                len -= 1
+            }
+            self.0.truncate(len)
+        }
+    }
+
+    /// Removes all content from the string.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Removes the last character from the string and returns it.
+    ///
+    /// Returns `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next_back()?;
+        self.0.truncate(self.len() - ch.len_utf8());
+        Some(ch)
+    }
+
+    /// Returns a writer that truncates the string if it overflows.
+    ///
+    /// The returned value implements `fmt::Write` and never returns an
+    /// error. Instead, it drops everything that doesn’t fit anymore.
+    pub fn truncating(&mut self) -> Truncating<'_, N> {
+        Truncating { target: self, truncated: false }
+    }
+}
+
+
+//--- TryFrom and FromStr
+
+impl<'a, const N: usize> TryFrom<&'a str> for ArrayString<N> {
+    type Error = ShortBuf;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Array::try_from(s.as_bytes()).map(Self)
+    }
+}
+
+impl<const N: usize> str::FromStr for ArrayString<N> {
+    type Err = ShortBuf;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+
+//--- Deref, DerefMut, AsRef, AsMut, Borrow, BorrowMut
+
+impl<const N: usize> ops::Deref for ArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> ops::DerefMut for ArrayString<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_str_mut()
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArrayString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsMut<str> for ArrayString<N> {
+    fn as_mut(&mut self) -> &mut str {
+        self.as_str_mut()
+    }
+}
+
+impl<const N: usize> borrow::Borrow<str> for ArrayString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> borrow::BorrowMut<str> for ArrayString<N> {
+    fn borrow_mut(&mut self) -> &mut str {
+        self.as_str_mut()
+    }
+}
+
+
+//--- Write
+
+impl<const N: usize> fmt::Write for ArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+
+//--- Debug and Display
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+
+//--- PartialEq and Eq
+
+impl<const N: usize, Other: AsRef<str>> PartialEq<Other> for ArrayString<N> {
+    fn eq(&self, other: &Other) -> bool {
+        self.as_str().eq(other.as_ref())
+    }
+}
+
+impl<const N: usize> Eq for ArrayString<N> { }
+
+
+//--- Hash
+
+impl<const N: usize> hash::Hash for ArrayString<N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+
+//--- PartialOrd and Ord
+
+impl<const N: usize, Other> PartialOrd<Other> for ArrayString<N>
+where Other: AsRef<str> {
+    fn partial_cmp(&self, other: &Other) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
+    }
+}
+
+impl<const N: usize> Ord for ArrayString<N> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+
+//--- Zeroize
+
+#[cfg(feature = "zeroize")]
+impl<const N: usize> zeroize::Zeroize for ArrayString<N> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+
+//------------ Truncating ----------------------------------------------------
+
+/// A writer that appends to an array string, truncating on overflow.
+///
+/// A value of this type is returned by [`ArrayString::truncating`]. It
+/// implements `fmt::Write` and never fails. Once a string doesn’t fit into
+/// the remaining space of the array string, as much of it as possible is
+/// appended and all further writes are ignored.
+pub struct Truncating<'a, const N: usize> {
+    target: &'a mut ArrayString<N>,
+    truncated: bool,
+}
+
+impl<const N: usize> Truncating<'_, N> {
+    /// Returns whether anything had to be dropped so far.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<const N: usize> fmt::Write for Truncating<'_, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.truncated {
+            self.truncated = !self.target.push_str_truncating(s);
+        }
+        Ok(())
+    }
+}
+
+
+//------------ AsciiStr ------------------------------------------------------
+
+/// A fixed length ASCII string atop an octets sequence.
+///
+/// The type guarantees that the octets sequence only contains ASCII
+/// characters, i.e., octets with values below 128. This makes it a valid
+/// UTF-8 string, too, so it can be converted into a [`Str`] for free and
+/// dereferences into a [`str`][prim@str].
+///
+/// The implementations of `PartialEq`, `Ord`, and `Hash` compare the
+/// octets exactly. Use [`eq_ignore_case`][Self::eq_ignore_case],
+/// [`cmp_ignore_case`][Self::cmp_ignore_case], and
+/// [`hash_ignore_case`][Self::hash_ignore_case] for comparisons that
+/// ignore ASCII case.
+#[derive(Clone, Default)]
+#[repr(transparent)]
+pub struct AsciiStr<Octets: ?Sized>(Octets);
+
+impl<Octets> AsciiStr<Octets> {
+    /// Converts a sequence of octets into an ASCII string.
+    ///
+    /// Returns an error if the octets contain any non-ASCII octets.
+    pub fn from_ascii(
+        octets: Octets
+    ) -> Result<Self, FromAsciiError<Octets>>
+    where Octets: AsRef<[u8]> {
+        match check_ascii(octets.as_ref()) {
+            Ok(()) => Ok(Self(octets)),
+            Err(valid_up_to) => Err(FromAsciiError { octets, valid_up_to })
+        }
+    }
+
+    /// Converts a sequence of octets into an ASCII string without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that `octets` only contains ASCII
+    /// characters.
+    pub const unsafe fn from_ascii_unchecked(octets: Octets) -> Self {
+        Self(octets)
+    }
+
+    /// Converts the string into its raw octets.
+    pub fn into_octets(self) -> Octets {
+        self.0
+    }
+
+    /// Converts the ASCII string into a UTF-8 string.
+    pub fn into_str(self) -> Str<Octets> {
+        Str(self.0)
+    }
+}
+
+impl AsciiStr<[u8]> {
+    /// Creates an ASCII string from an octets slice.
+    pub fn from_ascii_slice(
+        slice: &[u8]
+    ) -> Result<&Self, FromAsciiError<&[u8]>> {
+        match check_ascii(slice) {
+            Ok(()) => Ok(unsafe { &*(slice as *const [u8] as *const Self) }),
+            Err(valid_up_to) => {
+                Err(FromAsciiError { octets: slice, valid_up_to })
+            }
+        }
+    }
+}
+
+impl<Octets: ?Sized> AsciiStr<Octets> {
+    /// Returns the string as a string slice.
+    pub fn as_str(&self) -> &str
+    where Octets: AsRef<[u8]> {
+        unsafe { str::from_utf8_unchecked(self.0.as_ref()) }
+    }
+
+    /// Returns a reference to the underlying octets sequence.
+    pub fn as_octets(&self) -> &Octets {
+        &self.0
+    }
+
+    /// Returns the string’s octets as a slice.
+    pub fn as_slice(&self) -> &[u8]
+    where Octets: AsRef<[u8]> {
+        self.0.as_ref()
+    }
+
+    /// Returns the length of the string in octets.
+    pub fn len(&self) -> usize
+    where Octets: AsRef<[u8]> {
+        self.0.as_ref().len()
+    }
+
+    /// Returns whether the string is empty.
+    pub fn is_empty(&self) -> bool
+    where Octets: AsRef<[u8]> {
+        self.0.as_ref().is_empty()
+    }
+
+    /// Converts the string to lower case in place.
+    pub fn make_ascii_lowercase(&mut self)
+    where Octets: AsMut<[u8]> {
+        self.0.as_mut().make_ascii_lowercase()
+    }
+
+    /// Converts the string to upper case in place.
+    pub fn make_ascii_uppercase(&mut self)
+    where Octets: AsMut<[u8]> {
+        self.0.as_mut().make_ascii_uppercase()
+    }
+
+    /// Returns whether the string equals `other` ignoring ASCII case.
+    pub fn eq_ignore_case(&self, other: &(impl AsRef<[u8]> + ?Sized)) -> bool
+    where Octets: AsRef<[u8]> {
+        self.as_slice().eq_ignore_ascii_case(other.as_ref())
+    }
+
+    /// Compares the string with `other` ignoring ASCII case.
+    pub fn cmp_ignore_case(
+        &self, other: &(impl AsRef<[u8]> + ?Sized)
+    ) -> cmp::Ordering
+    where Octets: AsRef<[u8]> {
+        cmp_ignore_ascii_case(self.as_slice(), other.as_ref())
+    }
+
+    /// Feeds the string into a hasher ignoring ASCII case.
+    ///
+    /// Strings that are equal according to
+    /// [`eq_ignore_case`][Self::eq_ignore_case] result in the same hash.
+    pub fn hash_ignore_case<H: hash::Hasher>(&self, state: &mut H)
+    where Octets: AsRef<[u8]> {
+        hash_ignore_ascii_case(self.as_slice(), state)
+    }
+}
+
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<AsciiStr<SrcOcts>> for AsciiStr<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(src: AsciiStr<SrcOcts>) -> Result<Self, Self::Error> {
+        Octs::try_octets_from(src.into_octets()).map(|octs| unsafe {
+            Self::from_ascii_unchecked(octs)
+        })
+    }
+}
+
+
+//--- From
+
+impl<Octets> From<AsciiStr<Octets>> for Str<Octets> {
+    fn from(src: AsciiStr<Octets>) -> Self {
+        src.into_str()
+    }
+}
+
+
+//--- Deref, AsRef, Borrow
+
+impl<Octets: AsRef<[u8]> + ?Sized> ops::Deref for AsciiStr<Octets> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<Octets: AsRef<[u8]> + ?Sized> AsRef<str> for AsciiStr<Octets>{
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<Octets: AsRef<[u8]> + ?Sized> AsRef<[u8]> for AsciiStr<Octets>{
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<Octets: AsRef<[u8]> + ?Sized> borrow::Borrow<str> for AsciiStr<Octets>{
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+
+//--- Debug and Display
+
+impl<Octets: AsRef<[u8]> + ?Sized> fmt::Debug for AsciiStr<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<Octets: AsRef<[u8]> + ?Sized> fmt::Display for AsciiStr<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+
+//--- PartialEq and Eq
+
+impl<Octets, Other> PartialEq<Other> for AsciiStr<Octets>
+where
+    Octets: AsRef<[u8]> + ?Sized,
+    Other: AsRef<str> + ?Sized,
+{
+    fn eq(&self, other: &Other) -> bool {
+        self.as_str().eq(other.as_ref())
+    }
+}
+
+impl<Octets: AsRef<[u8]> + ?Sized> Eq for AsciiStr<Octets> { }
+
+
+//--- Hash
+
+impl<Octets: AsRef<[u8]> + ?Sized> hash::Hash for AsciiStr<Octets> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+
+//--- PartialOrd and Ord
+
+impl<Octets, Other> PartialOrd<Other> for AsciiStr<Octets>
+where
+    Octets: AsRef<[u8]> + ?Sized,
+    Other: AsRef<str> + ?Sized,
+{
+    fn partial_cmp(&self, other: &Other) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
+    }
+}
+
+impl<Octets: AsRef<[u8]> + ?Sized> Ord for AsciiStr<Octets> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+
+//--- Zeroize
+
+/// Wipes the string by zeroizing the underlying octets sequence.
+///
+/// Zeroizing octets either sets them to zero or removes them altogether.
+/// In both cases the result is still ASCII.
+#[cfg(feature = "zeroize")]
+impl<Octets> zeroize::Zeroize for AsciiStr<Octets>
+where Octets: zeroize::Zeroize + ?Sized {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+
+//--- Serialize and Deserialize
+
+#[cfg(feature = "serde")]
+impl<Octets> serde::Serialize for AsciiStr<Octets>
+where Octets: AsRef<[u8]> + ?Sized {
+    fn serialize<S: serde::Serializer>(
+        &self, serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Octets> serde::Deserialize<'de> for AsciiStr<Octets>
+where Octets: crate::serde::DeserializeOctets<'de> + AsRef<[u8]> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        Self::from_ascii(
+            Str::<Octets>::deserialize(deserializer)?.into_octets()
+        ).map_err(serde::de::Error::custom)
+    }
+}
+
+
+//------------ AsciiStrBuilder -----------------------------------------------
+
+/// A growable ASCII string atop an octets builder.
+#[derive(Clone)]
+pub struct AsciiStrBuilder<Octets>(Octets);
+
+impl<Octets> AsciiStrBuilder<Octets> {
+    /// Creates a new, empty string builder.
+    pub fn new() -> Self
+    where Octets: EmptyBuilder {
+        AsciiStrBuilder(Octets::empty())
+    }
+
+    /// Creates a new, empty string builder with a given minimum capacity.
+    pub fn with_capacity(capacity: usize) -> Self
+    where Octets: EmptyBuilder {
+        AsciiStrBuilder(Octets::with_capacity(capacity))
+    }
+
+    /// Creates a new string builder from an octets builder.
+    ///
+    /// Returns an error if the octets builder contains non-ASCII octets.
+    pub fn from_ascii(
+        octets: Octets
+    ) -> Result<Self, FromAsciiError<Octets>>
+    where Octets: AsRef<[u8]> {
+        match check_ascii(octets.as_ref()) {
+            Ok(()) => Ok(Self(octets)),
+            Err(valid_up_to) => Err(FromAsciiError { octets, valid_up_to })
+        }
+    }
+
+    /// Converts an octets builder into a string builder without checking.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `octets` only contains ASCII characters.
+    pub unsafe fn from_ascii_unchecked(octets: Octets) -> Self {
+        Self(octets)
+    }
+
+    /// Converts the string builder into the underlying octets builder.
+    pub fn into_octets_builder(self) -> Octets {
+        self.0
+    }
+
+    /// Converts the ASCII string builder into a UTF-8 string builder.
+    pub fn into_str_builder(self) -> StrBuilder<Octets> {
+        StrBuilder(self.0)
+    }
+
+    /// Converts the string builder into the final ASCII string.
+    pub fn freeze(self) -> AsciiStr<Octets::Octets>
+    where Octets: FreezeBuilder {
+        AsciiStr(self.0.freeze())
+    }
+
+    /// Returns a slice of the already assembled string.
+    pub fn as_str(&self) -> &str
+    where Octets: AsRef<[u8]> {
+        unsafe { str::from_utf8_unchecked(self.0.as_ref()) }
+    }
+
+    /// Returns the string’s octets as a slice.
+    pub fn as_slice(&self) -> &[u8]
+    where Octets: AsRef<[u8]> {
+        self.0.as_ref()
+    }
+
+    /// Returns the length of the string in octets.
+    pub fn len(&self) -> usize
+    where Octets: AsRef<[u8]> {
+        self.0.as_ref().len()
+    }
+
+    /// Returns whether the string is empty.
+    pub fn is_empty(&self) -> bool
+    where Octets: AsRef<[u8]> {
+        self.0.as_ref().is_empty()
+    }
+
+    /// Appends a given string slice onto the end of this builder.
+    ///
+    /// Returns an error and leaves the builder unchanged if `s` contains
+    /// non-ASCII characters.
+    pub fn try_push_str(
+        &mut self, s: &str,
+    ) -> Result<(), AppendAsciiError<Octets::AppendError>>
+    where Octets: OctetsBuilder {
+        check_ascii(s.as_bytes()).map_err(AppendAsciiError::NotAscii)?;
+        self.0.append_slice(s.as_bytes()).map_err(AppendAsciiError::ShortBuf)
+    }
+
+    /// Appends the given character to the end of the builder.
+    ///
+    /// Returns an error if `ch` is not an ASCII character.
+    pub fn try_push(
+        &mut self, ch: char
+    ) -> Result<(), AppendAsciiError<Octets::AppendError>>
+    where Octets: OctetsBuilder {
+        if !ch.is_ascii() {
+            return Err(AppendAsciiError::NotAscii(0))
+        }
+        self.0.append_slice(&[ch as u8]).map_err(AppendAsciiError::ShortBuf)
+    }
+
+    /// Truncates the builder, keeping the first `new_len` octets.
+    pub fn truncate(&mut self, new_len: usize)
+    where Octets: Truncate {
+        self.0.truncate(new_len)
+    }
+
+    /// Clears the builder into an empty builder.
+    pub fn clear(&mut self)
+    where Octets: Truncate {
+        self.truncate(0)
+    }
+
+    /// Removes the last character from the builder and returns it.
+    ///
+    /// Returns `None` if the builder is empty.
+    pub fn pop(&mut self) -> Option<char>
+    where Octets: AsRef<[u8]> + Truncate {
+        let ch = *self.as_slice().last()?;
+        self.truncate(self.len() - 1);
+        Some(ch.into())
+    }
+
+    /// Converts the string to lower case in place.
+    pub fn make_ascii_lowercase(&mut self)
+    where Octets: AsMut<[u8]> {
+        self.0.as_mut().make_ascii_lowercase()
+    }
+
+    /// Converts the string to upper case in place.
+    pub fn make_ascii_uppercase(&mut self)
+    where Octets: AsMut<[u8]> {
+        self.0.as_mut().make_ascii_uppercase()
+    }
+}
+
+
+//-- Default
+
+impl<Octets: EmptyBuilder> Default for AsciiStrBuilder<Octets> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+//--- OctetsFrom
+
+impl<Octs, SrcOcts> OctetsFrom<AsciiStrBuilder<SrcOcts>>
+for AsciiStrBuilder<Octs>
+where
+    Octs: OctetsFrom<SrcOcts>
+{
+    type Error = Octs::Error;
+
+    fn try_octets_from(
+        src: AsciiStrBuilder<SrcOcts>
+    ) -> Result<Self, Self::Error> {
+        Octs::try_octets_from(src.into_octets_builder()).map(|octs| unsafe {
+            Self::from_ascii_unchecked(octs)
+        })
+    }
+}
+
+
+//--- Deref, AsRef, Borrow
+
+impl<Octets: AsRef<[u8]>> ops::Deref for AsciiStrBuilder<Octets> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<Octets: AsRef<[u8]>> AsRef<str> for AsciiStrBuilder<Octets>{
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<Octets: AsRef<[u8]>> AsRef<[u8]> for AsciiStrBuilder<Octets>{
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<Octets: AsRef<[u8]>> borrow::Borrow<str> for AsciiStrBuilder<Octets>{
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+
+//--- Debug and Display
+
+impl<Octets: AsRef<[u8]>> fmt::Debug for AsciiStrBuilder<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<Octets: AsRef<[u8]>> fmt::Display for AsciiStrBuilder<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+
+//--- PartialEq and Eq
+
+impl<Octets, Other> PartialEq<Other> for AsciiStrBuilder<Octets>
+where
+    Octets: AsRef<[u8]>,
+    Other: AsRef<str>,
+{
+    fn eq(&self, other: &Other) -> bool {
+        self.as_str().eq(other.as_ref())
+    }
+}
+
+impl<Octets: AsRef<[u8]>> Eq for AsciiStrBuilder<Octets> { }
+
+
+//--- Hash
+
+impl<Octets: AsRef<[u8]>> hash::Hash for AsciiStrBuilder<Octets> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+
+//--- PartialOrd and Ord
+
+impl<Octets, Other> PartialOrd<Other> for AsciiStrBuilder<Octets>
+where
+    Octets: AsRef<[u8]>,
+    Other: AsRef<str>,
+{
+    fn partial_cmp(&self, other: &Other) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
+    }
+}
+
+impl<Octets: AsRef<[u8]>> Ord for AsciiStrBuilder<Octets> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+
+//--- Zeroize
+
+#[cfg(feature = "zeroize")]
+impl<Octets: zeroize::Zeroize> zeroize::Zeroize for AsciiStrBuilder<Octets> {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+
+//--- Serialize and Deserialize
+
+#[cfg(feature = "serde")]
+impl<Octets: AsRef<[u8]>> serde::Serialize for AsciiStrBuilder<Octets> {
+    fn serialize<S: serde::Serializer>(
+        &self, serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Octets> serde::Deserialize<'de> for AsciiStrBuilder<Octets>
+where Octets: crate::serde::DeserializeOctets<'de> + AsRef<[u8]> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        AsciiStr::<Octets>::deserialize(deserializer).map(|s| {
+            AsciiStrBuilder(s.into_octets())
+        })
+    }
+}
+
+
+//------------ CaseInsensitive -----------------------------------------------
+
+/// A wrapper comparing and hashing a value ignoring ASCII case.
+///
+/// The wrapper can be used with any type that implements `AsRef<[u8]>`,
+/// including [`Str`] and [`AsciiStr`]. Its implementations of `PartialEq`,
+/// `Ord`, and `Hash` compare the octets of the wrapped value with ASCII
+/// letters folded to lower case. This makes it possible to use values as
+/// keys in hash maps or B-tree maps that ignore case without having to
+/// create lowercase copies.
+///
+/// Owned values can be borrowed as `CaseInsensitive<[u8]>`, so maps can
+/// be queried with a reference created via
+/// [`from_ref`][Self::from_ref]:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use octseq::str::CaseInsensitive;
+///
+/// let mut map = HashMap::new();
+/// map.insert(CaseInsensitive::new(Vec::from(b"Content-Type")), 1);
+/// assert_eq!(
+///     map.get(CaseInsensitive::from_ref(b"content-type".as_ref())),
+///     Some(&1)
+/// );
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct CaseInsensitive<T: ?Sized>(T);
+
+impl<T> CaseInsensitive<T> {
+    /// Wraps a value.
+    pub const fn new(value: T) -> Self {
+        CaseInsensitive(value)
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: ?Sized> CaseInsensitive<T> {
+    /// Wraps a reference to a value.
+    pub fn from_ref(value: &T) -> &Self {
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub fn as_inner(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the wrapped value.
+    pub fn as_inner_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+
+//--- Deref and Borrow
+
+impl<T: ?Sized> ops::Deref for CaseInsensitive<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_inner()
+    }
+}
+
+impl<T: AsRef<[u8]>> borrow::Borrow<CaseInsensitive<[u8]>>
+for CaseInsensitive<T> {
+    fn borrow(&self) -> &CaseInsensitive<[u8]> {
+        CaseInsensitive::from_ref(self.0.as_ref())
+    }
+}
+
+
+//--- PartialEq and Eq
+
+impl<T, U> PartialEq<CaseInsensitive<U>> for CaseInsensitive<T>
+where
+    T: AsRef<[u8]> + ?Sized,
+    U: AsRef<[u8]> + ?Sized,
+{
+    fn eq(&self, other: &CaseInsensitive<U>) -> bool {
+        self.0.as_ref().eq_ignore_ascii_case(other.0.as_ref())
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Eq for CaseInsensitive<T> { }
+
+
+//--- PartialOrd and Ord
+
+impl<T, U> PartialOrd<CaseInsensitive<U>> for CaseInsensitive<T>
+where
+    T: AsRef<[u8]> + ?Sized,
+    U: AsRef<[u8]> + ?Sized,
+{
+    fn partial_cmp(
+        &self, other: &CaseInsensitive<U>
+    ) -> Option<cmp::Ordering> {
+        Some(cmp_ignore_ascii_case(self.0.as_ref(), other.0.as_ref()))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Ord for CaseInsensitive<T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        cmp_ignore_ascii_case(self.0.as_ref(), other.0.as_ref())
+    }
+}
+
+
+//--- Hash
+
+impl<T: AsRef<[u8]> + ?Sized> hash::Hash for CaseInsensitive<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        hash_ignore_ascii_case(self.0.as_ref(), state)
+    }
+}
+
+
+//--- Debug and Display
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for CaseInsensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CaseInsensitive").field(&&self.0).finish()
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for CaseInsensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+
+//------------ EscapeStyle ---------------------------------------------------
+
+/// The style of escape sequences used by [`escape`] and [`unescape`].
+///
+/// In all styles, a backslash starts an escape sequence.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EscapeStyle {
+    /// Escape sequences of the C programming language.
+    ///
+    /// When escaping, the backslash and double quote are escaped as `\\`
+    /// and `\"`, line feed, carriage return, and tab as `\n`, `\r`, and
+    /// `\t`, and all other octets that aren’t printable ASCII characters
+    /// as `\xHH` with two lowercase hex digits.
+    ///
+    /// When unescaping, `\xHH` must have exactly two hex digits and `\ooo`
+    /// one to three octal digits with a value of at most 255. The simple
+    /// escapes `\a`, `\b`, `\f`, `\n`, `\r`, `\t`, `\v`, `\\`, `\'`,
+    /// `\"`, and `\?` are accepted, too.
+    C,
+
+    /// Escape sequences of the DNS presentation format.
+    ///
+    /// When escaping, the backslash and the characters with special
+    /// meaning in zone files – `"`, `.`, `(`, `)`, `;`, `@`, and `$` – are
+    /// escaped as `\X`. All octets that aren’t printable ASCII
+    /// characters, including the space, are escaped as `\DDD` with three
+    /// decimal digits.
+    ///
+    /// When unescaping, `\DDD` must have exactly three digits with a
+    /// value of at most 255. A backslash followed by any other character
+    /// produces that character.
+    Dns,
+
+    /// Escape sequences of JSON strings.
+    ///
+    /// When escaping, the backslash and double quote are escaped as `\\`
+    /// and `\"`, line feed, carriage return, tab, backspace, and form feed
+    /// as `\n`, `\r`, `\t`, `\b`, and `\f`, and all other octets that
+    /// aren’t printable ASCII characters as `\u00HH`.
+    ///
+    /// When unescaping, `\uXXXX` is interpreted as a single octet and
+    /// must therefore have a value of at most `00FF`. The simple escapes
+    /// listed above as well as `\/` are accepted, too.
+    Json,
+}
+
+impl EscapeStyle {
+    /// Escapes a single octet if necessary.
+    ///
+    /// Returns the escape sequence written into `buf` or `None` if the
+    /// octet can be used as is.
+    fn escape_octet(self, ch: u8, buf: &mut [u8; 6]) -> Option<&str> {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let simple = match (self, ch) {
+            (_, b'\\' | b'"') => Some(ch),
+            (EscapeStyle::Dns, b'.' | b'(' | b')' | b';' | b'@' | b'$') => {
+                Some(ch)
+            }
+            (EscapeStyle::Dns, 0x21..=0x7e) => return None,
+            (EscapeStyle::C | EscapeStyle::Json, 0x20..=0x7e) => {
+                return None
+            }
+            (EscapeStyle::C | EscapeStyle::Json, b'\n') => Some(b'n'),
+            (EscapeStyle::C | EscapeStyle::Json, b'\r') => Some(b'r'),
+            (EscapeStyle::C | EscapeStyle::Json, b'\t') => Some(b't'),
+            (EscapeStyle::Json, 0x08) => Some(b'b'),
+            (EscapeStyle::Json, 0x0c) => Some(b'f'),
+            _ => None
+        };
+        buf[0] = b'\\';
+        let len = match (simple, self) {
+            (Some(simple), _) => {
+                buf[1] = simple;
+                2
+            }
+            (None, EscapeStyle::C) => {
+                buf[1..4].copy_from_slice(&[
+                    b'x', HEX[usize::from(ch >> 4)], HEX[usize::from(ch & 0xF)]
+                ]);
+                4
+            }
+            (None, EscapeStyle::Dns) => {
+                buf[1..4].copy_from_slice(&[
+                    b'0' + ch / 100, b'0' + ch / 10 % 10, b'0' + ch % 10
+                ]);
+                4
+            }
+            (None, EscapeStyle::Json) => {
+                buf[1..6].copy_from_slice(&[
+                    b'u', b'0', b'0',
+                    HEX[usize::from(ch >> 4)], HEX[usize::from(ch & 0xF)]
+                ]);
+                6
+            }
+        };
+        // Escape sequences are all ASCII.
+        str::from_utf8(&buf[..len]).ok()
+    }
+
+    /// Unescapes the escape sequence at the start of `input`.
+    ///
+    /// The first octet of `input` must be the backslash. Returns the octet
+    /// and the length of the escape sequence. Returns the index of the
+    /// offending octet relative to `input` if the sequence is malformed.
+    fn unescape_octet(self, input: &[u8]) -> Result<(u8, usize), usize> {
+        /// Returns the value of the digit at `idx`.
+        fn digit(input: &[u8], idx: usize, radix: u32) -> Result<u8, usize> {
+            input.get(idx).and_then(|&ch| {
+                char::from(ch).to_digit(radix)
+            }).map(|value| value as u8).ok_or(idx)
+        }
+
+        let next = *input.get(1).ok_or(1usize)?;
+        let res = match (self, next) {
+            (EscapeStyle::C, b'x') => {
+                (digit(input, 2, 16)? << 4 | digit(input, 3, 16)?, 4)
+            }
+            (EscapeStyle::C, b'a') => (0x07, 2),
+            (EscapeStyle::C, b'v') => (0x0b, 2),
+            (EscapeStyle::C, b'0'..=b'7') => {
+                let mut value = 0u16;
+                let mut len = 1;
+                while len < 4 {
+                    let Ok(digit) = digit(input, len, 8) else { break };
+                    value = value << 3 | u16::from(digit);
+                    len += 1;
+                }
+                (u8::try_from(value).map_err(|_| 0usize)?, len)
+            }
+            (EscapeStyle::C, b'\'' | b'?') => (next, 2),
+            (EscapeStyle::Json, b'u') => {
+                if digit(input, 2, 16)? != 0 || digit(input, 3, 16)? != 0 {
+                    return Err(0)
+                }
+                (digit(input, 4, 16)? << 4 | digit(input, 5, 16)?, 6)
+            }
+            (EscapeStyle::Json, b'/') => (next, 2),
+            (EscapeStyle::C | EscapeStyle::Json, b'\\' | b'"') => (next, 2),
+            (EscapeStyle::C | EscapeStyle::Json, b'b') => (0x08, 2),
+            (EscapeStyle::C | EscapeStyle::Json, b'f') => (0x0c, 2),
+            (EscapeStyle::C | EscapeStyle::Json, b'n') => (b'\n', 2),
+            (EscapeStyle::C | EscapeStyle::Json, b'r') => (b'\r', 2),
+            (EscapeStyle::C | EscapeStyle::Json, b't') => (b'\t', 2),
+            (EscapeStyle::C | EscapeStyle::Json, _) => return Err(1),
+            (EscapeStyle::Dns, b'0'..=b'9') => {
+                let value = u16::from(digit(input, 1, 10)?) * 100
+                    + u16::from(digit(input, 2, 10)?) * 10
+                    + u16::from(digit(input, 3, 10)?);
+                (u8::try_from(value).map_err(|_| 0usize)?, 4)
+            }
+            (EscapeStyle::Dns, _) => (next, 2),
+        };
+        Ok(res)
+    }
+}
+
+
+//------------ escape and unescape -------------------------------------------
+
+/// Appends arbitrary octets in escaped form to a string builder.
+///
+/// All octets that cannot be used as is are replaced by escape sequences
+/// according to `style`. The resulting string only contains printable
+/// ASCII characters and can be converted back using [`unescape`] with the
+/// same style.
+pub fn escape<Octets: OctetsBuilder>(
+    input: impl AsRef<[u8]>,
+    style: EscapeStyle,
+    target: &mut StrBuilder<Octets>,
+) -> Result<(), Octets::AppendError> {
+    let input = input.as_ref();
+    let mut start = 0;
+    let mut buf = [0u8; 6];
+    for (pos, &ch) in input.iter().enumerate() {
+        let Some(escaped) = style.escape_octet(ch, &mut buf) else {
+            continue
+        };
+        // Octets that don’t need escaping are printable ASCII.
+        target.0.append_slice(&input[start..pos])?;
+        target.try_push_str(escaped)?;
+        start = pos + 1;
+    }
+    target.0.append_slice(&input[start..])
+}
+
+/// Decodes escaped text and appends the resulting octets to a builder.
+///
+/// Escape sequences are interpreted according to `style`. All other
+/// characters are appended in their UTF-8 encoding.
+///
+/// If the input contains a malformed escape sequence, returns
+/// [`DecodeError::Invalid`] with the index of the first offending octet
+/// in `input`. If the escape sequence is cut short by the end of the
+/// input, this is the length of the input. If the value of an otherwise
+/// well-formed escape sequence is out of range, it is the index of the
+/// backslash starting the sequence.
+pub fn unescape<Target: OctetsBuilder>(
+    input: impl AsRef<str>,
+    style: EscapeStyle,
+    target: &mut Target,
+) -> Result<(), DecodeError> {
+    let input = input.as_ref().as_bytes();
+    let mut start = 0;
+    while let Some(offset) = input[start..].iter().position(|&ch| {
+        ch == b'\\'
+    }) {
+        let pos = start + offset;
+        append(target, &input[start..pos])?;
+        if style == EscapeStyle::Dns && input.get(pos + 1) >= Some(&0x80) {
+            // A backslash followed by a non-ASCII character. Drop the
+            // backslash and keep the character as is. Since a backslash
+            // never appears inside a multi-octet character, we can just
+            // continue searching from the next octet.
+            append(target, &input[pos + 1..pos + 2])?;
+            start = pos + 2;
+            continue
+        }
+        let (octet, len) = style.unescape_octet(&input[pos..]).map_err(
+            |idx| DecodeError::Invalid(pos + idx)
+        )?;
+        append(target, &[octet])?;
+        start = pos + len;
+    }
+    append(target, &input[start..])
+}
+
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Removes the octets between `start` and `end` from an octets builder.
+fn remove_octets<Octets>(octets: &mut Octets, start: usize, end: usize)
+where Octets: AsRef<[u8]> + AsMut<[u8]> + Truncate {
+    let len = octets.as_ref().len();
+    octets.as_mut()[start..].rotate_left(end - start);
+    octets.truncate(len - (end - start));
+}
+
+/// Creates a string builder from as many characters of `s` as fit.
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+fn builder_from_str_truncating<Octets>(s: &str) -> StrBuilder<Octets>
+where Octets: OctetsBuilder + EmptyBuilder {
+    let mut res = StrBuilder::with_capacity(s.len());
+    for ch in s.chars() {
+        if res.try_push(ch).is_err() {
+            break
+        }
+    }
+    res
+}
+
+/// Checks that a slice only contains ASCII characters.
+///
+/// Returns the index of the first non-ASCII octet as the error.
+fn check_ascii(slice: &[u8]) -> Result<(), usize> {
+    match slice.iter().position(|ch| !ch.is_ascii()) {
+        Some(pos) => Err(pos),
+        None => Ok(())
+    }
+}
+
+/// Compares two octets slices ignoring ASCII case.
+fn cmp_ignore_ascii_case(left: &[u8], right: &[u8]) -> cmp::Ordering {
+    left.iter().map(u8::to_ascii_lowercase).cmp(
+        right.iter().map(u8::to_ascii_lowercase)
+    )
+}
+
+/// Feeds an octets slice to a hasher ignoring ASCII case.
+fn hash_ignore_ascii_case<H: hash::Hasher>(slice: &[u8], state: &mut H) {
+    state.write_usize(slice.len());
+    for ch in slice {
+        state.write_u8(ch.to_ascii_lowercase())
+    }
+}
+
+
+//============ Error Types ===================================================
+
+//------------ FromUtf8Error -------------------------------------------------
+
+/// An error happened when converting octets into a string.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct FromUtf8Error<Octets> {
+    octets: Octets,
+    error: str::Utf8Error,
+}
+
+impl<Octets> FromUtf8Error<Octets> {
+    /// Returns an octets slice of the data that failed to convert.
+    pub fn as_slice(&self) -> &[u8]
+    where Octets: AsRef<[u8]> {
+        self.octets.as_ref()
+    }
+
+    /// Returns the octets sequence that failed to convert.
+    pub fn into_octets(self) -> Octets {
+        self.octets
+    }
+
+    /// Returns the reason for the conversion error.
+    pub fn utf8_error(&self) -> str::Utf8Error {
+        self.error
+    }
+}
+
+impl<Octets> fmt::Debug for FromUtf8Error<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FromUtf8Error")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Octets> fmt::Display for FromUtf8Error<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<Octets> core::error::Error for FromUtf8Error<Octets> {}
+
+
+//------------ FromAsciiError ------------------------------------------------
+
+/// An error happened when converting octets into an ASCII string.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct FromAsciiError<Octets> {
+    octets: Octets,
+    valid_up_to: usize,
+}
+
+impl<Octets> FromAsciiError<Octets> {
+    /// Returns an octets slice of the data that failed to convert.
+    pub fn as_slice(&self) -> &[u8]
+    where Octets: AsRef<[u8]> {
+        self.octets.as_ref()
+    }
+
+    /// Returns the octets sequence that failed to convert.
+    pub fn into_octets(self) -> Octets {
+        self.octets
+    }
+
+    /// Returns the index of the first non-ASCII octet.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+}
+
+impl<Octets> fmt::Debug for FromAsciiError<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FromAsciiError")
+            .field("valid_up_to", &self.valid_up_to)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Octets> fmt::Display for FromAsciiError<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "non-ASCII octet at index {}", self.valid_up_to)
+    }
+}
+
+impl<Octets> core::error::Error for FromAsciiError<Octets> {}
+
+
+//------------ AppendAsciiError ----------------------------------------------
+
+/// An error happened when appending to an ASCII string builder.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AppendAsciiError<E> {
+    /// The appended data contained a non-ASCII octet at the given index.
+    NotAscii(usize),
+
+    /// The octets builder failed to append the data.
+    ShortBuf(E),
+}
+
+impl<E: fmt::Display> fmt::Display for AppendAsciiError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppendAsciiError::NotAscii(pos) => {
+                write!(f, "non-ASCII octet at index {pos}")
+            }
+            AppendAsciiError::ShortBuf(err) => err.fmt(f)
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error
+for AppendAsciiError<E> {}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Most of the test cases herein have been borrowed from the test cases
+    // of the Rust standard library.
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn zeroize() {
+        use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+        use crate::array::Array;
+
+        fn zeroize_on_drop<T: ZeroizeOnDrop>() { }
+
+        let mut s = Str::from_utf8(
+            Array::<8>::try_from(b"secret".as_ref()).unwrap()
+        ).unwrap();
+        Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+
+        let mut s = StrBuilder::<Array<8>>::new();
+        s.try_push_str("secret").unwrap();
+        Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+
+        zeroize_on_drop::<Str<Zeroizing<Array<8>>>>();
+        zeroize_on_drop::<StrBuilder<Zeroizing<Array<8>>>>();
+    }
+
+    #[test]
+    #[cfg(all(feature = "zeroize", feature = "alloc"))]
+    fn zeroize_vec() {
+        use zeroize::Zeroize;
+
+        let mut s = Str::from_utf8(alloc::vec::Vec::from(b"secret".as_ref()))
+            .unwrap();
+        Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+
+        let mut s = StrBuilder::<alloc::vec::Vec<u8>>::new();
+        s.push_str("secret");
+        Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+    }
+
+    #[test]
+    fn const_construction() {
+        const FROM_STATIC: Str<&[u8]> = Str::from_static("hello");
+        static FROM_UTF8: Str<&[u8]> = Str::from_utf8_static(b"f\xc3\xbc");
+        const SLICE: &Str<[u8]> = Str::from_str("hello");
+
+        assert_eq!(FROM_STATIC, "hello");
+        assert_eq!(FROM_UTF8, "fü");
+        assert_eq!(SLICE, "hello");
+        assert_eq!(static_str!("ศไทย"), "ศไทย");
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_utf8_static_invalid() {
+        let octets: &'static [u8] = b"\xc3";
+        let _ = Str::from_utf8_static(octets);
+    }
+
+    #[test]
+    fn array_string() {
+        use core::fmt::Write;
+
+        let mut s = ArrayString::<8>::new();
+        s.push_str("abc").unwrap();
+        s.push('€').unwrap();
+        assert_eq!(s, "abc€");
+        assert_eq!(s.remaining_capacity(), 2);
+        assert_eq!(s.push('€'), Err(ShortBuf));
+        assert!(write!(s, "{}", 123).is_err());
+        assert_eq!(s, "abc€");
+        write!(s, "{}", 12).unwrap();
+        assert_eq!(s, "abc€12");
+        assert_eq!(s.pop(), Some('2'));
+        s.truncate_floor(4);
+        assert_eq!(s, "abc");
+
+        assert_eq!("abcdefgh".parse::<ArrayString<8>>().unwrap(), "abcdefgh");
+        assert_eq!("abcdefghi".parse::<ArrayString<8>>(), Err(ShortBuf));
+        assert!(ArrayString::<4>::from_utf8((*b"ab\xff").into()).is_err());
+    }
+
+    #[test]
+    fn array_string_truncating() {
+        use core::fmt::Write;
+
+        assert_eq!(ArrayString::<4>::from_str_truncating("abc€"), "abc");
+
+        let mut s = ArrayString::<8>::new();
+        let ch = 'ü';
+        let mut w = s.truncating();
+        write!(w, "abcd-{ch}").unwrap();
+        assert!(!w.is_truncated());
+        write!(w, "{ch}x").unwrap();
+        assert!(w.is_truncated());
+        write!(w, "y").unwrap();
+        assert_eq!(s, "abcd-ü");
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn array_string_zeroize() {
+        let mut s = ArrayString::<8>::new();
+        s.push_str("secret").unwrap();
+        zeroize::Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+        s.push_str("ab").unwrap();
+        assert_eq!(s, "ab");
+    }
+
+    #[test]
+    fn ascii_str() {
+        assert_eq!(
+            AsciiStr::from_ascii(b"ab\xc3\xbc".as_ref()).unwrap_err()
+                .valid_up_to(),
+            2
+        );
+        let s = AsciiStr::from_ascii_slice(b"Example").unwrap();
+        assert_eq!(s, "Example");
+        assert!(s.eq_ignore_case("eXAMPLE"));
+        assert!(!s.eq_ignore_case("example."));
+        assert_eq!(s.cmp_ignore_case("EXAMPLF"), cmp::Ordering::Less);
+        assert_eq!(s.cmp_ignore_case(b"example"), cmp::Ordering::Equal);
+
+        let mut s = AsciiStr::from_ascii(Array::<8>::from(*b"Example"))
+            .unwrap();
+        s.make_ascii_lowercase();
+        assert_eq!(s, "example");
+        assert_eq!(s.into_str(), "example");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn ascii_str_hash() {
+        use core::hash::{BuildHasher, Hasher};
+
+        let state = std::hash::RandomState::new();
+        let hash = |s: &AsciiStr<[u8]>| {
+            let mut hasher = state.build_hasher();
+            s.hash_ignore_case(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(
+            hash(AsciiStr::from_ascii_slice(b"Example").unwrap()),
+            hash(AsciiStr::from_ascii_slice(b"eXAMPLE").unwrap()),
+        );
+    }
+
+    #[test]
+    fn ascii_str_builder() {
+        let mut s = AsciiStrBuilder::<Array<4>>::new();
+        s.try_push_str("ab").unwrap();
+        assert_eq!(s.try_push_str("ü"), Err(AppendAsciiError::NotAscii(0)));
+        assert_eq!(s.try_push('ü'), Err(AppendAsciiError::NotAscii(0)));
+        s.try_push('C').unwrap();
+        assert_eq!(
+            s.try_push_str("de"), Err(AppendAsciiError::ShortBuf(ShortBuf))
+        );
+        s.make_ascii_uppercase();
+        assert_eq!(s, "ABC");
+        assert_eq!(s.pop(), Some('C'));
+        assert_eq!(s.freeze(), "AB");
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn ascii_str_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut s = AsciiStr::from_ascii(Array::<8>::from(*b"secret"))
+            .unwrap();
+        Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+
+        let mut s = AsciiStrBuilder::<Array<8>>::new();
+        s.try_push_str("secret").unwrap();
+        Zeroize::zeroize(&mut s);
+        assert_eq!(s, "");
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut s = AsciiStrBuilder::<alloc::vec::Vec<u8>>::new();
+            s.try_push_str("secret").unwrap();
+            Zeroize::zeroize(&mut s);
+            assert_eq!(s, "");
+        }
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let lower = CaseInsensitive::new(Str::from_static("example"));
+        let upper = CaseInsensitive::from_ref("EXAMPLE");
+        assert_eq!(lower, *upper);
+        assert_eq!(lower, CaseInsensitive::new(b"eXaMpLe"));
+        assert_ne!(lower, CaseInsensitive::new(b"example."));
+        assert!(lower < CaseInsensitive::new("EXAMPLF"));
+        assert!(lower > CaseInsensitive::new("EXAMPL"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn case_insensitive_maps() {
+        use std::collections::{BTreeMap, HashMap};
+        use std::vec::Vec;
+
+        let mut map = HashMap::new();
+        map.insert(CaseInsensitive::new(Vec::from(b"Example")), 1);
+        map.insert(CaseInsensitive::new(Vec::from(b"EXAMPLE")), 2);
+        assert_eq!(map.len(), 1);
+        assert_eq!(
+            map.get(CaseInsensitive::from_ref(b"example".as_ref())),
+            Some(&2)
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert(CaseInsensitive::new(Vec::from(b"b")), 1);
+        map.insert(CaseInsensitive::new(Vec::from(b"A")), 2);
+        map.insert(CaseInsensitive::new(Vec::from(b"a")), 3);
+        assert_eq!(
+            map.into_values().collect::<Vec<_>>(),
+            [3, 1]
+        );
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut s = StrBuilder::<Array<8>>::new();
+        s.try_insert_str(0, "ac").unwrap();
+        s.try_insert(1, 'ü').unwrap();
+        s.try_insert(4, 'd').unwrap();
+        assert_eq!(s, "aücd");
+        assert_eq!(s.try_insert_str(0, "€€"), Err(ShortBuf));
+        assert_eq!(s, "aücd");
+        assert_eq!(s.remove(1), 'ü');
+        assert_eq!(s.remove(2), 'd');
+        assert_eq!(s, "ac");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn insert_replace_vec() {
+        let mut s = StrBuilder::<alloc::vec::Vec<u8>>::new();
+        s.insert_str(0, "ศไทย");
+        s.insert(0, '中');
+        s.replace_range(3..6, "Việt Nam");
+        assert_eq!(s, "中Việt Namไทย");
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_not_char_boundary() {
+        let mut s = StrBuilder::<Array<8>>::new();
+        s.try_push('ü').unwrap();
+        let _ = s.try_insert(1, 'a');
+    }
+
+    #[test]
+    #[should_panic(expected = "range end out of bounds")]
+    fn drain_range_overflow() {
+        let mut s = StrBuilder::<Array<8>>::new();
+        s.try_push_str("abc").unwrap();
+        let _ = s.drain(..=usize::MAX);
+    }
+
+    #[test]
+    fn replace_range() {
+        let mut s = StrBuilder::<Array<12>>::new();
+        s.try_push_str("abcdef").unwrap();
+        s.try_replace_range(1..3, "€").unwrap();
+        assert_eq!(s, "a€def");
+        s.try_replace_range(1..4, "B").unwrap();
+        assert_eq!(s, "aBdef");
+        s.try_replace_range(..2, "").unwrap();
+        assert_eq!(s, "def");
+        s.try_replace_range(3.., "ghijklmno").unwrap();
+        assert_eq!(s, "defghijklmno");
+        assert_eq!(s.try_replace_range(..1, "xy"), Err(ShortBuf));
+        assert_eq!(s, "defghijklmno");
+    }
+
+    #[test]
+    fn retain() {
+        let mut s = StrBuilder::<Array<12>>::new();
+        s.try_push_str("aü€bc€").unwrap();
+        s.retain(|ch| ch != '€');
+        assert_eq!(s, "aübc");
+        s.retain(|ch| ch.is_ascii());
+        assert_eq!(s, "abc");
+    }
+
+    #[test]
+    fn drain() {
+        let mut s = StrBuilder::<Array<12>>::new();
+        s.try_push_str("aü€bc").unwrap();
+        let mut drain = s.drain(1..6);
+        assert_eq!(drain.next(), Some('ü'));
+        assert_eq!(drain.as_str(), "€");
+        drop(drain);
+        assert_eq!(s, "abc");
+        assert!(s.drain(..).rev().eq("abc".chars().rev()));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn escape_styles() {
+        fn check(style: EscapeStyle, octets: &[u8], escaped: &str) {
+            let mut s = StrBuilder::<Array<64>>::new();
+            escape(octets, style, &mut s).unwrap();
+            assert_eq!(s, escaped);
+            let mut octs = Array::<64>::new();
+            unescape(escaped, style, &mut octs).unwrap();
+            assert_eq!(octs, octets);
+        }
+
+        let octets = b"a \"b\\.c\"\n\x00\x7f\xff";
+        check(EscapeStyle::C, octets, r#"a \"b\\.c\"\n\x00\x7f\xff"#);
+        check(EscapeStyle::Dns, octets, r#"a\032\"b\\\.c\"\010\000\127\255"#);
+        check(
+            EscapeStyle::Json, octets,
+            r#"a \"b\\.c\"\n\u0000\u007f\u00ff"#
+        );
+    }
+
+    #[test]
+    fn unescape_other() {
+        fn check(style: EscapeStyle, escaped: &str, octets: &[u8]) {
+            let mut octs = Array::<64>::new();
+            unescape(escaped, style, &mut octs).unwrap();
+            assert_eq!(octs, octets);
+        }
+
+        check(EscapeStyle::C, r"\a\v\?\'ü", b"\x07\x0b?'\xc3\xbc");
+        check(EscapeStyle::C, r"\0\12\101\1012\08", b"\0\nAA2\x008");
+        check(EscapeStyle::Dns, r"\a\üb\\", b"a\xc3\xbcb\\");
+        check(EscapeStyle::Json, r"\/\b\f\u00e9é", b"/\x08\x0c\xe9\xc3\xa9");
+    }
+
+    #[test]
+    fn unescape_errors() {
+        fn check(style: EscapeStyle, escaped: &str, pos: usize) {
+            let mut octs = Array::<64>::new();
+            assert_eq!(
+                unescape(escaped, style, &mut octs),
+                Err(DecodeError::Invalid(pos))
+            );
+        }
+
+        check(EscapeStyle::C, r"ab\x4g", 5);
+        check(EscapeStyle::C, r"ab\x4", 5);
+        check(EscapeStyle::C, r"ab\q", 3);
+        check(EscapeStyle::C, "ab\\", 3);
+        check(EscapeStyle::C, r"ab\400", 2);
+        check(EscapeStyle::Dns, r"ab\25", 5);
+        check(EscapeStyle::Dns, r"ab\2a5", 4);
+        check(EscapeStyle::Dns, r"ab\256", 2);
+        check(EscapeStyle::Json, r"ab\u00g0", 6);
+        check(EscapeStyle::Json, r"ab\u0100", 2);
+        check(EscapeStyle::Json, r"ab\x", 3);
+
+        let mut octs = Array::<2>::new();
+        assert_eq!(
+            unescape(r"ab\000", EscapeStyle::Dns, &mut octs),
+            Err(DecodeError::ShortBuf)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_utf8_lossy() {
+        fn check(src: impl AsRef<[u8]>) {
+            assert_eq!(
+                StrBuilder::from_utf8_lossy(alloc::vec::Vec::from(src.as_ref())),
+                alloc::string::String::from_utf8_lossy(src.as_ref())
+            );
+        }
+
+        check(b"hello");
+        check("ศไทย中华Việt Nam");
+        check(b"Hello\xC2 There\xFF Goodbye");
+        check(b"Hello\xC0\x80 There\xE6\x83 Goodbye");
+        check(b"\xF5foo\xF5\x80bar");
+        check(b"\xF1foo\xF1\x80bar\xF1\x80\x80baz");
+        check(b"\xF4foo\xF4\x80bar\xF4\xBFbaz");
+        check(b"\xF0\x80\x80\x80foo\xF0\x90\x80\x80bar");
+        check(b"\xED\xA0\x80foo\xED\xBF\xBFbar");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn push_str() {
+        let mut s = StrBuilder::<alloc::vec::Vec<u8>>::new();
+        s.push_str("");
+        assert_eq!(&s[0..], "");
+        s.push_str("abc");
+        assert_eq!(&s[0..], "abc");
+        s.push_str("ประเทศไทย中华Việt Nam");
+        assert_eq!(&s[0..], "abcประเทศไทย中华Việt Nam");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn push() {
+        let mut data = StrBuilder::from_utf8(
+            alloc::vec::Vec::from("ประเทศไทย中".as_bytes())
+        ).unwrap();
+        data.push('华');
+        data.push('b'); // 1 byte
+        data.push('¢'); // 2 byte
+        data.push('€'); // 3 byte
+        data.push('𤭢'); // 4 byte
+        assert_eq!(data, "ประเทศไทย中华b¢€𤭢");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn pop() {
+        let mut data = StrBuilder::from_utf8(
+            alloc::vec::Vec::from("ประเทศไทย中华b¢€𤭢".as_bytes())
+        ).unwrap();
+        assert_eq!(data.pop().unwrap(), '𤭢'); // 4 bytes
+        assert_eq!(data.pop().unwrap(), '€'); // 3 bytes
+        assert_eq!(data.pop().unwrap(), '¢'); // 2 bytes
+        assert_eq!(data.pop().unwrap(), 'b'); // 1 bytes
+        assert_eq!(data.pop().unwrap(), '华');
+        assert_eq!(data, "ประเทศไทย中");
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        // The string "aé€" plus its length.
+        let data = b"a\xc3\xa9\xe2\x82\xac\x06";
+        let s = Str::<Array<4>>::arbitrary(
+            &mut Unstructured::new(data)
+        ).unwrap();
+        assert_eq!(s, "aé");
+        let s = StrBuilder::<Array<8>>::arbitrary(
+            &mut Unstructured::new(data)
+        ).unwrap();
+        assert_eq!(s, "aé€");
+        let array = Array::<4, u8>::arbitrary(
+            &mut Unstructured::new(data)
+        ).unwrap();
+        assert_eq!(array.as_slice(), b"a\xc3\xa9\xe2");
+    }
+}
@@ -0,0 +1,41 @@
+//! Regression tests replaying the fuzz corpus.
+//!
+//! The fuzz targets in `fuzz/` check `Parser` against a reference model and
+//! `StrBuilder::from_utf8_lossy` against the standard library. The inputs
+//! in `fuzz/regressions/<target>` are run through the same checks here so
+//! they are covered without a fuzzing toolchain. New crashes found by the
+//! fuzzer should be added there.
+#![cfg(feature = "std")]
+
+#[path = "../fuzz/src/lib.rs"]
+mod harness;
+
+use std::fs;
+use std::path::Path;
+
+/// Runs all inputs in the regression corpus of `target` through `check`.
+fn replay(target: &str, check: fn(&[u8])) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(
+        "fuzz/regressions"
+    ).join(target);
+    let mut count = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let data = fs::read(&path).unwrap();
+        if std::panic::catch_unwind(|| check(&data)).is_err() {
+            panic!("failed on {}", path.display());
+        }
+        count += 1;
+    }
+    assert!(count > 0);
+}
+
+#[test]
+fn parser() {
+    replay("parser", harness::parser);
+}
+
+#[test]
+fn utf8_lossy() {
+    replay("utf8_lossy", harness::utf8_lossy);
+}
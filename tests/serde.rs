@@ -0,0 +1,296 @@
+//! Conformance tests for serde support across actual formats.
+//!
+//! Every octets type with serde support is serialized through each of a
+//! set of formats and deserialized again. The formats differ in how they
+//! hand out bytes: serde_json produces sequences of `u8`, bincode and
+//! postcard provide borrowed bytes, and ciborium only ever provides
+//! transient or owned bytes.
+#![cfg(all(feature = "serde", feature = "std"))]
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use octseq::array::Array;
+use octseq::serde::{DeserializeOctets, SerializeOctets};
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+
+
+//------------ Formats -------------------------------------------------------
+
+/// A serialization format under test.
+trait Format {
+    /// Serializes a value.
+    fn serialize<T: Serialize>(value: &T) -> Vec<u8>;
+
+    /// Deserializes a value that doesn’t borrow from the input.
+    fn deserialize_owned<T: DeserializeOwned>(
+        data: &[u8]
+    ) -> Result<T, String>;
+
+    /// Deserializes a value that may borrow from the input.
+    fn deserialize_borrowed<'de, T: Deserialize<'de>>(
+        data: &'de [u8]
+    ) -> Result<T, String>;
+}
+
+struct Json;
+
+impl Format for Json {
+    fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+        serde_json::to_vec(value).unwrap()
+    }
+
+    fn deserialize_owned<T: DeserializeOwned>(
+        data: &[u8]
+    ) -> Result<T, String> {
+        serde_json::from_slice(data).map_err(|err| err.to_string())
+    }
+
+    fn deserialize_borrowed<'de, T: Deserialize<'de>>(
+        data: &'de [u8]
+    ) -> Result<T, String> {
+        serde_json::from_slice(data).map_err(|err| err.to_string())
+    }
+}
+
+struct Bincode;
+
+impl Format for Bincode {
+    fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+        bincode::serialize(value).unwrap()
+    }
+
+    fn deserialize_owned<T: DeserializeOwned>(
+        data: &[u8]
+    ) -> Result<T, String> {
+        bincode::deserialize(data).map_err(|err| err.to_string())
+    }
+
+    fn deserialize_borrowed<'de, T: Deserialize<'de>>(
+        data: &'de [u8]
+    ) -> Result<T, String> {
+        bincode::deserialize(data).map_err(|err| err.to_string())
+    }
+}
+
+struct Postcard;
+
+impl Format for Postcard {
+    fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+        postcard::to_allocvec(value).unwrap()
+    }
+
+    fn deserialize_owned<T: DeserializeOwned>(
+        data: &[u8]
+    ) -> Result<T, String> {
+        postcard::from_bytes(data).map_err(|err| err.to_string())
+    }
+
+    fn deserialize_borrowed<'de, T: Deserialize<'de>>(
+        data: &'de [u8]
+    ) -> Result<T, String> {
+        postcard::from_bytes(data).map_err(|err| err.to_string())
+    }
+}
+
+struct Cbor;
+
+impl Format for Cbor {
+    fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+        let mut res = Vec::new();
+        ciborium::into_writer(value, &mut res).unwrap();
+        res
+    }
+
+    fn deserialize_owned<T: DeserializeOwned>(
+        data: &[u8]
+    ) -> Result<T, String> {
+        ciborium::from_reader(data).map_err(|err| err.to_string())
+    }
+
+    fn deserialize_borrowed<'de, T: Deserialize<'de>>(
+        data: &'de [u8]
+    ) -> Result<T, String> {
+        // ciborium can only deserialize owned types directly. Going via
+        // its `Value` gives us a deserializer that never lends out data.
+        ciborium::from_reader::<ciborium::Value, _>(data).map_err(|err| {
+            err.to_string()
+        })?.deserialized().map_err(|err| err.to_string())
+    }
+}
+
+
+//------------ Wrapped -------------------------------------------------------
+
+/// A struct with an octets field, the way octets are used in practice.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "T: SerializeOctets",
+    deserialize = "T: DeserializeOctets<'de>",
+))]
+struct Wrapped<T> {
+    #[serde(with = "octseq::serde::bytes")]
+    octets: T,
+}
+
+impl<T> Wrapped<T> {
+    fn new(octets: T) -> Self {
+        Wrapped { octets }
+    }
+}
+
+
+//------------ Helpers -------------------------------------------------------
+
+/// Some octets used as test data.
+///
+/// They contain a zero octet and octets that aren’t valid UTF-8 to catch
+/// formats or visitors that go via strings.
+const DATA: &[u8] = b"\x00\x01oct\xfe\xff";
+
+/// Serializes the octets and checks that the output is that of `DATA`.
+fn serialize<F: Format, T: SerializeOctets>(octets: T) -> Vec<u8> {
+    let res = F::serialize(&Wrapped::new(octets));
+    assert_eq!(res, F::serialize(&Wrapped::new(DATA)));
+    res
+}
+
+/// Round-trips an octets type that owns its data.
+fn owned<F, T>(octets: T)
+where
+    F: Format,
+    T: SerializeOctets + AsRef<[u8]> + Debug,
+    for<'de> T: DeserializeOctets<'de>,
+{
+    let data = serialize::<F, _>(octets);
+    let res: Wrapped<T> = F::deserialize_owned(&data).unwrap();
+    assert_eq!(res.octets.as_ref(), DATA);
+}
+
+/// Round-trips all owned types through a format.
+fn all_owned<F: Format>() {
+    owned::<F, _>(Vec::from(DATA));
+    owned::<F, _>(Array::<16>::try_from(DATA).unwrap());
+    owned::<F, _>(Array::<7>::try_from(DATA).unwrap());
+    owned::<F, _>(Array::<7, u8>::try_from(DATA).unwrap());
+    #[cfg(feature = "bytes")]
+    owned::<F, _>(bytes::Bytes::from_static(DATA));
+    #[cfg(feature = "heapless")]
+    {
+        owned::<F, _>(heapless::Vec::<u8, 16>::from_slice(DATA).unwrap());
+        owned::<F, _>(heapless::Vec::<u8, 7>::from_slice(DATA).unwrap());
+    }
+    #[cfg(feature = "smallvec")]
+    {
+        owned::<F, _>(smallvec::SmallVec::<[u8; 16]>::from_slice(DATA));
+        // Spills onto the heap.
+        owned::<F, _>(smallvec::SmallVec::<[u8; 2]>::from_slice(DATA));
+    }
+}
+
+/// Checks borrowed and `Cow` deserialization.
+///
+/// If `borrows` is true, the format must be able to provide borrowed
+/// octets and a `Cow` must come out borrowed. Otherwise, deserializing
+/// a `&[u8]` must fail and a `Cow` must come out owned.
+fn borrowed<F: Format>(borrows: bool) {
+    let data = serialize::<F, _>(DATA);
+    assert_eq!(serialize::<F, _>(Cow::Borrowed(DATA)), data);
+    assert_eq!(serialize::<F, _>(Cow::<[u8]>::Owned(DATA.into())), data);
+
+    match F::deserialize_borrowed::<Wrapped<&[u8]>>(&data) {
+        Ok(res) => {
+            assert!(borrows);
+            assert_eq!(res.octets, DATA);
+        }
+        Err(_) => assert!(!borrows),
+    }
+
+    let res = F::deserialize_borrowed::<Wrapped<Cow<[u8]>>>(
+        &data
+    ).unwrap().octets;
+    assert_eq!(res.as_ref(), DATA);
+    assert_eq!(matches!(res, Cow::Borrowed(_)), borrows);
+}
+
+/// Checks that bounded types reject too long octets.
+fn overlength<F: Format>() {
+    let data = serialize::<F, _>(DATA);
+
+    assert!(F::deserialize_owned::<Wrapped<Array<6>>>(&data).is_err());
+    assert!(F::deserialize_owned::<Wrapped<Array<6, u8>>>(&data).is_err());
+    assert!(F::deserialize_owned::<Wrapped<Array<0>>>(&data).is_err());
+    #[cfg(feature = "heapless")]
+    assert!(
+        F::deserialize_owned::<Wrapped<heapless::Vec<u8, 6>>>(
+            &data
+        ).is_err()
+    );
+
+    // Empty octets fit everywhere.
+    let data = serialize_empty::<F>();
+    let res: Wrapped<Array<0>> = F::deserialize_owned(&data).unwrap();
+    assert!(res.octets.is_empty());
+    #[cfg(feature = "heapless")]
+    {
+        let res: Wrapped<heapless::Vec<u8, 0>>
+            = F::deserialize_owned(&data).unwrap();
+        assert!(res.octets.is_empty());
+    }
+}
+
+fn serialize_empty<F: Format>() -> Vec<u8> {
+    F::serialize(&Wrapped::new(b"".as_ref()))
+}
+
+
+//------------ Tests ---------------------------------------------------------
+
+#[test]
+fn json() {
+    all_owned::<Json>();
+    borrowed::<Json>(false);
+    overlength::<Json>();
+}
+
+#[test]
+fn bincode() {
+    all_owned::<Bincode>();
+    borrowed::<Bincode>(true);
+    overlength::<Bincode>();
+}
+
+#[test]
+fn postcard() {
+    all_owned::<Postcard>();
+    borrowed::<Postcard>(true);
+    overlength::<Postcard>();
+}
+
+#[test]
+fn cbor() {
+    all_owned::<Cbor>();
+    borrowed::<Cbor>(false);
+    overlength::<Cbor>();
+}
+
+#[test]
+fn json_strings() {
+    // Octets can also be read from strings. serde_json provides borrowed
+    // strings if there are no escape sequences.
+    let res: Wrapped<&[u8]>
+        = serde_json::from_str(r#"{"octets":"oct"}"#).unwrap();
+    assert_eq!(res.octets, b"oct");
+    let res: Wrapped<Cow<[u8]>>
+        = serde_json::from_str(r#"{"octets":"o\nt"}"#).unwrap();
+    assert!(matches!(res.octets, Cow::Owned(_)));
+    assert_eq!(res.octets.as_ref(), b"o\nt");
+    let res: Wrapped<Array<3>>
+        = serde_json::from_str(r#"{"octets":"oct"}"#).unwrap();
+    assert_eq!(res.octets.as_ref(), b"oct");
+    assert!(
+        serde_json::from_str::<Wrapped<Array<2>>>(
+            r#"{"octets":"oct"}"#
+        ).is_err()
+    );
+}